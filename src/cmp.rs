@@ -0,0 +1,213 @@
+//! Comparison, hashing and formatting of subsets by values of selected items.
+//! 
+//! Two subsets are equal if they select equal values in the same order,
+//! no matter which sets they borrow and which indexes they select.
+//! Use `identity_eq` to check whether two subsets select the very same items.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use subset::multi::Subset;
+//! 
+//! let set = [1, 2, 1];
+//! let a = Subset::new(&set, &[0, 1]).unwrap();
+//! let b = Subset::new(&set, &[2, 1]).unwrap();
+//! assert_eq!(a, b);
+//! assert!(!a.identity_eq(&b));
+//! assert_eq!(a, [1, 2][..]);
+//! assert_eq!(format!("{:?}", a), "[1, 2]");
+//! ```
+
+use super::{compressed, matrix, multi, ranges, strided, unique, Idx, SubsetView};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+
+macro_rules! impl_value_traits {
+    ([$($gen:tt)*] $ty:ty) => {
+        impl<'a, T: Debug, $($gen)*> Debug for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(SubsetView::iter(self)).finish()
+            }
+        }
+
+        impl<'a, T: PartialEq, $($gen)*> PartialEq for $ty {
+            fn eq(&self, other: &Self) -> bool {
+                SubsetView::len(self) == SubsetView::len(other) && SubsetView::iter(self).eq(SubsetView::iter(other))
+            }
+        }
+
+        impl<'a, T: Eq, $($gen)*> Eq for $ty {}
+
+        impl<'a, T: PartialEq, $($gen)*> PartialEq<[T]> for $ty {
+            fn eq(&self, other: &[T]) -> bool {
+                SubsetView::len(self) == other.len() && SubsetView::iter(self).eq(other)
+            }
+        }
+
+        impl<'a, T: PartialOrd, $($gen)*> PartialOrd for $ty {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                SubsetView::iter(self).partial_cmp(SubsetView::iter(other))
+            }
+        }
+
+        impl<'a, T: Ord, $($gen)*> Ord for $ty {
+            fn cmp(&self, other: &Self) -> Ordering {
+                SubsetView::iter(self).cmp(SubsetView::iter(other))
+            }
+        }
+
+        // Consistent with `Eq`: hashes the number of selected items, then every selected value
+        impl<'a, T: Hash, $($gen)*> Hash for $ty {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                SubsetView::len(self).hash(state);
+                SubsetView::iter(self).for_each(|v| v.hash(state));
+            }
+        }
+    };
+}
+
+impl_value_traits!([I: Idx] multi::Subset<'a, T, I>);
+impl_value_traits!([I: Idx] multi::SubsetMut<'a, T, I>);
+impl_value_traits!([I: Idx] unique::Subset<'a, T, I>);
+impl_value_traits!([I: Idx] unique::SubsetMut<'a, T, I>);
+impl_value_traits!([const N: usize] unique::FixedSubsetMut<'a, T, N>);
+impl_value_traits!([] strided::StridedSubset<'a, T>);
+impl_value_traits!([] strided::StridedSubsetMut<'a, T>);
+impl_value_traits!([] ranges::RangeSubset<'a, T>);
+impl_value_traits!([] ranges::RangeSubsetMut<'a, T>);
+impl_value_traits!([] compressed::CompressedSubset<'a, T>);
+impl_value_traits!([] compressed::CompressedSubsetMut<'a, T>);
+#[cfg(feature = "roaring")]
+impl_value_traits!([] compressed::RoaringSubset<'a, T>);
+#[cfg(feature = "roaring")]
+impl_value_traits!([] compressed::RoaringSubsetMut<'a, T>);
+
+
+// Matrix views print selected rows, each being a slice or a subset of selected columns
+macro_rules! impl_matrix_debug {
+    ($($ty:ident),+) => {
+        $(
+            impl<'a, T: Debug + 'a, D: Deref<Target = [T]> + matrix::MatrixData> Debug for matrix::$ty<'a, D> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_list().entries(self.iter()).finish()
+                }
+            }
+        )+
+    };
+}
+
+impl_matrix_debug!(RowSubset, ColSubset, Submatrix);
+
+
+#[cfg(feature = "ndarray")]
+impl<'a, A: Debug, D: ndarray::RemoveAxis> Debug for super::nd::Subset<'a, A, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+
+#[cfg(feature = "ndarray")]
+impl<'a, A: Debug, D: ndarray::RemoveAxis> Debug for super::nd::SubsetMut<'a, A, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+
+// Prints the items left to yield, without advancing
+impl<'a, T: Debug, I: Iterator<Item = usize> + Clone> Debug for multi::LazySubset<'a, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+
+macro_rules! impl_identity_eq {
+    ($ty:ty, $m:ident) => {
+        impl<'a, T, I: Idx> $ty {
+            /// Checks that both subsets borrow the same set and select the same indexes in the same order.
+            pub fn identity_eq(&self, other: &Self) -> bool {
+                std::ptr::eq(&*self.$m.set, &*other.$m.set) && self.$m.idxs == other.$m.idxs
+            }
+        }
+    };
+    ($ty:ty) => {
+        impl<'a, T, I: Idx> $ty {
+            /// Checks that both subsets borrow the same set and select the same indexes in the same order.
+            pub fn identity_eq(&self, other: &Self) -> bool {
+                std::ptr::eq(&*self.set, &*other.set) && self.idxs == other.idxs
+            }
+        }
+    };
+}
+
+impl_identity_eq!(multi::Subset<'a, T, I>);
+impl_identity_eq!(multi::SubsetMut<'a, T, I>);
+impl_identity_eq!(unique::Subset<'a, T, I>, m);
+impl_identity_eq!(unique::SubsetMut<'a, T, I>, m);
+
+
+macro_rules! impl_view_identity_eq {
+    ([$($gen:tt)*] $ty:ty) => {
+        impl<'a, T, $($gen)*> $ty {
+            /// Checks that both subsets borrow the same set and select the same indexes in the same order.
+            pub fn identity_eq(&self, other: &Self) -> bool {
+                std::ptr::eq(&*self.set, &*other.set) && SubsetView::idxs(self).eq(SubsetView::idxs(other))
+            }
+        }
+    };
+}
+
+impl_view_identity_eq!([const N: usize] unique::FixedSubsetMut<'a, T, N>);
+impl_view_identity_eq!([] strided::StridedSubset<'a, T>);
+impl_view_identity_eq!([] strided::StridedSubsetMut<'a, T>);
+impl_view_identity_eq!([] ranges::RangeSubset<'a, T>);
+impl_view_identity_eq!([] ranges::RangeSubsetMut<'a, T>);
+impl_view_identity_eq!([] compressed::CompressedSubset<'a, T>);
+impl_view_identity_eq!([] compressed::CompressedSubsetMut<'a, T>);
+#[cfg(feature = "roaring")]
+impl_view_identity_eq!([] compressed::RoaringSubset<'a, T>);
+#[cfg(feature = "roaring")]
+impl_view_identity_eq!([] compressed::RoaringSubsetMut<'a, T>);
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_cmp() {
+        let mut set = vec![3, 1, 2, 1];
+        let idxs = [1, 2];
+        let a = unique::Subset::new(&set, &idxs).unwrap();
+        let b = unique::Subset::from_vec(&set, vec![3, 2]).unwrap();
+        let c = unique::Subset::new(&set, &idxs).unwrap();
+        assert_eq!(a, b);
+        assert!(!a.identity_eq(&b));
+        assert!(a.identity_eq(&c));
+        assert!(unique::Subset::new(&set, &[0]).unwrap() > a);
+        assert!(a < unique::Subset::new(&set, &[1, 2, 3]).unwrap());
+        assert_eq!(a, [1, 2][..]);
+        assert_eq!(format!("{:?}", b), "[1, 2]");
+        let mut values = HashSet::new();
+        values.insert(strided::StridedSubset::new(&set, 1, 2, 2).unwrap());
+        assert!(values.contains(&strided::StridedSubset::new(&set, 3, 0, 2).unwrap()));
+        let a = strided::StridedSubset::new(&set, 1, 2, 1).unwrap();
+        assert!(a.identity_eq(&strided::StridedSubset::new(&set, 1, 5, 1).unwrap()));
+        assert!(!a.identity_eq(&strided::StridedSubset::new(&set[..3], 1, 2, 1).unwrap()));
+        let ranges = ranges::RangeList::from_idxs(&[0, 1]).unwrap();
+        let a = ranges::RangeSubset::new(&set, ranges.clone()).unwrap();
+        assert!(a.identity_eq(&ranges::RangeSubset::new(&set, ranges).unwrap()));
+        assert_eq!(format!("{:?}", matrix::RowSubset::new(&set[..], 2, &[1]).unwrap()), "[[2, 1]]");
+        assert_eq!(format!("{:?}", matrix::ColSubset::new(&set[..], 2, &[1]).unwrap()), "[[1], [1]]");
+        assert_eq!(format!("{:?}", multi::LazySubset::new(&set, [3, 9])), "[Ok(1), Err(OutOfBounds)]");
+        let subset = strided::StridedSubsetMut::new(&mut set, 0, 1, 2).unwrap();
+        assert_eq!(format!("{:?}", subset), "[3, 1]");
+    }
+}
@@ -0,0 +1,299 @@
+//! Unique subsets selecting items by a roaring bitmap of indexes.
+
+use roaring::RoaringBitmap;
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use super::SubsetError;
+
+
+/// Checks that every index of `bitmap` is `< len`.
+fn check_bounds(bitmap: &RoaringBitmap, len: usize) -> Result<(), SubsetError> {
+    match bitmap.max() {
+        Some(max) if max as usize >= len => Err(SubsetError::OutOfBounds),
+        _ => Ok(())
+    }
+}
+
+
+/// Subset of slice's items selected by a roaring bitmap
+/// that is able to iterate forward and backward over immutable references to selected items in ascending order of indexes.
+/// Each item of a slice can be selected no more than once.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::compressed::*;
+/// use roaring::RoaringBitmap;
+/// 
+/// let set = [0, 10, 20, 30, 40];
+/// let bitmap: RoaringBitmap = [4, 1, 3].iter().copied().collect();
+/// let subset = RoaringSubset::new(&set, &bitmap).unwrap();
+/// assert_eq!(subset.iter().collect::<Vec<_>>(), vec![&10, &30, &40]);
+/// assert_eq!(subset[2], 40);
+/// ```
+pub struct RoaringSubset<'a, T> {
+    pub(crate) set: &'a [T],
+    pub(crate) bitmap: &'a RoaringBitmap
+}
+
+
+impl<'a, T> RoaringSubset<'a, T> {
+    /// Constructs a subset from the whole set and a bitmap of indexes of the selected items.
+    /// Array bounds is checked in O(1).
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`.
+    pub fn new(set: &'a [T], bitmap: &'a RoaringBitmap) -> Result<Self, SubsetError> {
+        check_bounds(bitmap, set.len())?;
+        Ok(Self { set, bitmap })
+    }
+    /// Returns the original slice.
+    pub fn set(&self) -> &[T] {
+        self.set
+    }
+    /// Returns the bitmap of indexes of selected items.
+    pub fn bitmap(&self) -> &'a RoaringBitmap {
+        self.bitmap
+    }
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.bitmap.len() as usize
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.bitmap.is_empty()
+    }
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get(&self, pos: usize) -> Option<&'a T> {
+        let idx = self.bitmap.select(u32::try_from(pos).ok()?)?;
+        Some(&self.set[idx as usize])
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> RoaringIter<'a, T> {
+        RoaringIter {
+            set: self.set,
+            idxs: self.bitmap.iter()
+        }
+    }
+}
+
+
+impl<'a, T> From<RoaringSubsetMut<'a, T>> for RoaringSubset<'a, T> {
+    fn from(s: RoaringSubsetMut<'a, T>) -> Self {
+        Self {
+            set: s.set,
+            bitmap: s.bitmap
+        }
+    }
+}
+
+
+impl<'a, T> Index<usize> for RoaringSubset<'a, T> {
+    type Output = T;
+    fn index(&self, pos: usize) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IntoIterator for &RoaringSubset<'a, T> {
+    type Item = &'a T;
+    type IntoIter = RoaringIter<'a, T>;
+    fn into_iter(self) -> RoaringIter<'a, T> {
+        self.iter()
+    }
+}
+
+
+/// Double-ended iterator over immutable references to selected items of set.
+pub struct RoaringIter<'a, T> {
+    set: &'a [T],
+    idxs: roaring::bitmap::Iter<'a>
+}
+
+
+impl<'a, T> Iterator for RoaringIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.idxs.next().map(|idx| &self.set[idx as usize])
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idxs.size_hint()
+    }
+}
+
+
+impl<'a, T> DoubleEndedIterator for RoaringIter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.idxs.next_back().map(|idx| &self.set[idx as usize])
+    }
+}
+
+
+/// Subset of slice's items selected by a roaring bitmap
+/// that is able to iterate forward and backward over mutable or immutable references to selected items in ascending order of indexes.
+/// Each item of a slice can be selected no more than once.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::compressed::*;
+/// use roaring::RoaringBitmap;
+/// 
+/// let mut set = [0, 10, 20, 30, 40];
+/// let bitmap: RoaringBitmap = (1..4).collect();
+/// let mut subset = RoaringSubsetMut::new(&mut set, &bitmap).unwrap();
+/// for v in subset.iter_mut() {
+///     *v += 1;
+/// }
+/// assert_eq!(set, [0, 11, 21, 31, 40]);
+/// ```
+pub struct RoaringSubsetMut<'a, T> {
+    pub(crate) set: &'a mut [T],
+    pub(crate) bitmap: &'a RoaringBitmap
+}
+
+
+impl<'a, T> RoaringSubsetMut<'a, T> {
+    /// Constructs a subset from the whole set and a bitmap of indexes of the selected items.
+    /// Array bounds is checked in O(1), the uniqueness is implied by the bitmap.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`.
+    pub fn new(set: &'a mut [T], bitmap: &'a RoaringBitmap) -> Result<Self, SubsetError> {
+        check_bounds(bitmap, set.len())?;
+        Ok(Self { set, bitmap })
+    }
+    /// Returns the original slice.
+    pub fn set(&mut self) -> &mut [T] {
+        self.set
+    }
+    /// Returns the bitmap of indexes of selected items.
+    pub fn bitmap(&self) -> &'a RoaringBitmap {
+        self.bitmap
+    }
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.bitmap.len() as usize
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.bitmap.is_empty()
+    }
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        let idx = self.bitmap.select(u32::try_from(pos).ok()?)?;
+        Some(&self.set[idx as usize])
+    }
+    /// Returns a mutable reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        let idx = self.bitmap.select(u32::try_from(pos).ok()?)?;
+        Some(&mut self.set[idx as usize])
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> RoaringIter<'_, T> {
+        RoaringIter {
+            set: self.set,
+            idxs: self.bitmap.iter()
+        }
+    }
+    /// Returns an iterator over mutable references to selected items.
+    pub fn iter_mut(&mut self) -> RoaringIterMut<'_, T> {
+        RoaringIterMut {
+            ptr: self.set.as_mut_ptr(),
+            idxs: self.bitmap.iter(),
+            _marker: PhantomData
+        }
+    }
+}
+
+
+impl<'a, T> Index<usize> for RoaringSubsetMut<'a, T> {
+    type Output = T;
+    fn index(&self, pos: usize) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IndexMut<usize> for RoaringSubsetMut<'a, T> {
+    fn index_mut(&mut self, pos: usize) -> &mut T {
+        self.get_mut(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a RoaringSubsetMut<'a, T> {
+    type Item = &'a T;
+    type IntoIter = RoaringIter<'a, T>;
+    fn into_iter(self) -> RoaringIter<'a, T> {
+        self.iter()
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a mut RoaringSubsetMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = RoaringIterMut<'a, T>;
+    fn into_iter(self) -> RoaringIterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+
+/// Double-ended iterator over mutable references to selected items of set.
+pub struct RoaringIterMut<'a, T> {
+    ptr: *mut T,    // Points to the set
+    idxs: roaring::bitmap::Iter<'a>,
+    _marker: PhantomData<&'a mut T>
+}
+
+
+impl<'a, T: 'a> Iterator for RoaringIterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<&'a mut T> {
+        let idx = self.idxs.next()?;
+        Some(unsafe{&mut *self.ptr.add(idx as usize)})
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idxs.size_hint()
+    }
+}
+
+
+impl<'a, T: 'a> DoubleEndedIterator for RoaringIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        let idx = self.idxs.next_back()?;
+        Some(unsafe{&mut *self.ptr.add(idx as usize)})
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{SubsetView, SubsetViewMut};
+
+    #[test]
+    fn test_roaring() {
+        let mut set = vec![0, 1, 2, 3, 4, 5];
+        let bitmap: RoaringBitmap = [2, 6].iter().copied().collect();
+        assert_eq!(RoaringSubset::new(&set, &bitmap).err(), Some(SubsetError::OutOfBounds));
+        let bitmap: RoaringBitmap = [5, 0, 2].iter().copied().collect();
+        let mut subset = RoaringSubsetMut::new(&mut set, &bitmap).unwrap();
+        assert_eq!(subset.len(), 3);
+        assert_eq!(subset.get(3), None);
+        subset[1] = 20;
+        for v in subset.iter_mut().rev().take(2) {
+            *v *= 10;
+        }
+        let subset: RoaringSubset<_> = subset.into();
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![0, 200, 50]);
+        assert_eq!(SubsetView::idxs(&subset).collect::<Vec<_>>(), vec![0, 2, 5]);
+        let mut subset = RoaringSubsetMut::new(&mut set, &bitmap).unwrap();
+        subset.fill(7);
+        assert_eq!(subset.gather(), vec![7, 7, 7]);
+    }
+}
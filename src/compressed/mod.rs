@@ -0,0 +1,613 @@
+//! Subsets whose indexes are stored compressed, for selections of millions of items.
+//! 
+//! `CompressedIdxs` stores differences between neighbouring indexes as zigzag-encoded varints,
+//! so sorted or clustered index lists take about one byte per index.
+//! It is iterated forward and backward without decompressing everything,
+//! and positional access decodes at most one block of `BLOCK` indexes.
+//! 
+//! Like `multi::Subset`, a `CompressedSubset` may select an item more than once,
+//! while `CompressedSubsetMut`, like `unique::SubsetMut`, selects every item no more than once.
+//! Both convert from and to these subsets, compressing or decompressing the indexes.
+//! 
+//! `multi` and `unique` subsets are not generic over the index storage:
+//! they hand out their indexes as `&[I]` and rely on O(1) positional access in `Index`, `get` and splitting,
+//! which a varint stream cannot provide. Compressed subsets are therefore separate types
+//! with the same iteration API, and converting is the way to switch between the two representations.
+//! 
+//! With the `roaring` feature, `RoaringSubset` and `RoaringSubsetMut` select items by a `roaring::RoaringBitmap`.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use subset::compressed::*;
+//! 
+//! let mut set: Vec<u64> = (0..100_000).collect();
+//! let idxs: CompressedIdxs = (0..100_000).step_by(3).collect();
+//! assert!(idxs.compressed_len() < idxs.len() + 16);
+//! assert_eq!(idxs.get(1000), Some(3000));
+//! 
+//! let mut subset = CompressedSubsetMut::new(&mut set, &idxs).unwrap();
+//! for v in subset.iter_mut() {
+//!     *v = 0;
+//! }
+//! assert_eq!(subset.iter().next_back(), Some(&0));
+//! assert_eq!(set.iter().filter(|v| **v == 0).count(), idxs.len());
+//! 
+//! let subset: subset::unique::SubsetMut<_> = CompressedSubsetMut::new(&mut set, &idxs).unwrap().into();
+//! assert_eq!(subset.idxs()[1000], 3000);
+//! ```
+
+pub use std::convert::{From,Into,TryFrom,TryInto};
+use std::borrow::Cow;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use super::{is_unique, multi, unique, Idx};
+pub use super::SubsetError;
+
+#[cfg(feature = "roaring")]
+mod bitmap;
+#[cfg(feature = "roaring")]
+pub use bitmap::*;
+
+
+/// Number of indexes between two seek checkpoints.
+pub const BLOCK: usize = 64;
+
+
+/// Maps differences of any sign to small unsigned numbers.
+fn zigzag(delta: isize) -> usize {
+    ((delta << 1) ^ (delta >> (isize::BITS - 1))) as usize
+}
+
+
+fn unzigzag(v: usize) -> isize {
+    ((v >> 1) as isize) ^ -((v & 1) as isize)
+}
+
+
+/// Decodes the varint starting at `bytes[*offset]` and moves `offset` past it.
+fn read_forward(bytes: &[u8], offset: &mut usize) -> usize {
+    let mut v = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*offset];
+        *offset += 1;
+        v |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return v;
+        }
+        shift += 7;
+    }
+}
+
+
+/// Decodes the varint ending right before `bytes[*end]` and moves `end` to its start.
+fn read_backward(bytes: &[u8], end: &mut usize) -> usize {
+    // Only the last byte of a varint has the high bit cleared
+    let mut start = *end - 1;
+    while start > 0 && bytes[start - 1] & 0x80 != 0 {
+        start -= 1;
+    }
+    let mut offset = start;
+    *end = start;
+    read_forward(bytes, &mut offset)
+}
+
+
+/// Compressed list of indexes.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::compressed::*;
+/// let mut idxs = CompressedIdxs::new();
+/// for idx in [1_000_000, 5, 6, 7] {
+///     idxs.push(idx);
+/// }
+/// assert_eq!(idxs.iter().rev().collect::<Vec<_>>(), vec![7, 6, 5, 1_000_000]);
+/// assert_eq!(idxs.max_len(), 1_000_001);
+/// ```
+#[derive(Debug,Clone,Default,PartialEq,Eq,Hash)]
+pub struct CompressedIdxs {
+    bytes: Vec<u8>,
+    checkpoints: Vec<(usize, usize)>,    // Byte offset of every BLOCK-th index and the index before it
+    len: usize,
+    last: usize,
+    max_len: usize
+}
+
+
+impl CompressedIdxs {
+    /// Constructs an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Appends an index to the end of the list.
+    // `usize::is_multiple_of` is only stable since Rust 1.87, newer than the compilers this crate supports
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn push(&mut self, idx: usize) {
+        if self.len % BLOCK == 0 {
+            self.checkpoints.push((self.bytes.len(), self.last));
+        }
+        let mut v = zigzag(idx.wrapping_sub(self.last) as isize);
+        while v >= 0x80 {
+            self.bytes.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        self.bytes.push(v as u8);
+        self.len += 1;
+        self.last = idx;
+        self.max_len = self.max_len.max(idx.saturating_add(1));
+    }
+    /// Returns the number of indexes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if the list has no indexes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns the number of bytes used by the compressed indexes.
+    pub fn compressed_len(&self) -> usize {
+        self.bytes.len()
+    }
+    /// Returns the maximal index plus one, i.e. the minimal length of slices the list fits in.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+    /// Returns the `pos`-th index or `None` if `pos >= len()`.
+    /// Seeks to the closest checkpoint and decodes at most `BLOCK` indexes.
+    pub fn get(&self, pos: usize) -> Option<usize> {
+        if pos >= self.len {
+            return None;
+        }
+        let (mut offset, mut idx) = self.checkpoints[pos / BLOCK];
+        for _ in 0..=pos % BLOCK {
+            idx = idx.wrapping_add(unzigzag(read_forward(&self.bytes, &mut offset)) as usize);
+        }
+        Some(idx)
+    }
+    /// Returns a double-ended iterator over indexes.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            bytes: &self.bytes,
+            front: (0, 0),
+            back: (self.bytes.len(), self.last),
+            len: self.len
+        }
+    }
+    /// Checks that no index occurs twice or more.
+    pub fn is_unique(&self) -> bool {
+        if self.max_len / 64 > self.len {
+            // A bitmap of sparse indexes would be too large
+            return is_unique(&self.to_vec());
+        }
+        let mut bitmap = vec![0u64; self.max_len.div_ceil(64)];
+        self.iter().all(|idx| {
+            let (word, bit) = (idx / 64, 1 << (idx % 64));
+            let fresh = bitmap[word] & bit == 0;
+            bitmap[word] |= bit;
+            fresh
+        })
+    }
+    /// Decompresses all indexes.
+    pub fn to_vec(&self) -> Vec<usize> {
+        self.iter().collect()
+    }
+}
+
+
+impl FromIterator<usize> for CompressedIdxs {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut idxs = Self::new();
+        iter.into_iter().for_each(|idx| idxs.push(idx));
+        idxs
+    }
+}
+
+
+impl From<&[usize]> for CompressedIdxs {
+    fn from(idxs: &[usize]) -> Self {
+        idxs.iter().copied().collect()
+    }
+}
+
+
+impl<'s> IntoIterator for &'s CompressedIdxs {
+    type Item = usize;
+    type IntoIter = Iter<'s>;
+    fn into_iter(self) -> Iter<'s> {
+        self.iter()
+    }
+}
+
+
+/// Double-ended iterator over compressed indexes.
+#[derive(Debug,Clone)]
+pub struct Iter<'s> {
+    bytes: &'s [u8],
+    front: (usize, usize),    // Byte offset of the next index and the index before it
+    back: (usize, usize),     // Byte offset past the next index from the back and that index
+    len: usize
+}
+
+
+impl<'s> Iterator for Iter<'s> {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let (offset, prev) = &mut self.front;
+        *prev = prev.wrapping_add(unzigzag(read_forward(self.bytes, offset)) as usize);
+        Some(*prev)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+
+impl<'s> ExactSizeIterator for Iter<'s> {}
+
+
+impl<'s> DoubleEndedIterator for Iter<'s> {
+    fn next_back(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let (end, idx) = &mut self.back;
+        let current = *idx;
+        *idx = idx.wrapping_sub(unzigzag(read_backward(self.bytes, end)) as usize);
+        Some(current)
+    }
+}
+
+
+/// Multi-subset of slice's items selected by compressed indexes
+/// that is able to iterate forward and backward over immutable references to selected items.
+/// Each item of a slice can be selected more than once.
+pub struct CompressedSubset<'a, T> {
+    pub(crate) set: &'a [T],
+    pub(crate) idxs: Cow<'a, CompressedIdxs>
+}
+
+
+impl<'a, T> CompressedSubset<'a, T> {
+    /// Constructs a multi-subset from the whole set and compressed indexes of the selected items.
+    /// Array bounds is checked in O(1).
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new(set: &'a [T], idxs: &'a CompressedIdxs) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        if idxs.max_len() > set.len() {
+            Err(SubsetError::OutOfBounds)
+        } else { Ok(Self { set, idxs: Cow::Borrowed(idxs) }) }
+    }
+    /// Returns the original slice.
+    pub fn set(&self) -> &[T] {
+        self.set
+    }
+    /// Returns compressed indexes of selected items.
+    pub fn idxs(&self) -> &CompressedIdxs {
+        &self.idxs
+    }
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.idxs.len()
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.idxs.is_empty()
+    }
+    /// Checks that no items are selected twice or more.
+    pub fn is_unique(&self) -> bool {
+        self.idxs.is_unique()
+    }
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        self.idxs.get(pos).map(|idx| &self.set[idx])
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> SubsetIter<'_, T> {
+        SubsetIter {
+            set: self.set,
+            idxs: self.idxs.iter()
+        }
+    }
+}
+
+
+impl<'a, T> From<CompressedSubsetMut<'a, T>> for CompressedSubset<'a, T> {
+    fn from(s: CompressedSubsetMut<'a, T>) -> Self {
+        Self {
+            set: s.set,
+            idxs: s.idxs
+        }
+    }
+}
+
+
+impl<'a, T, I: Idx> From<multi::Subset<'a, T, I>> for CompressedSubset<'a, T> {
+    fn from(s: multi::Subset<'a, T, I>) -> Self {
+        Self {
+            set: s.set,
+            idxs: Cow::Owned(s.idxs.iter().map(|idx| idx.index()).collect())
+        }
+    }
+}
+
+
+impl<'a, T, I: Idx> From<unique::Subset<'a, T, I>> for CompressedSubset<'a, T> {
+    fn from(s: unique::Subset<'a, T, I>) -> Self {
+        s.m.into()
+    }
+}
+
+
+impl<'a, T> From<CompressedSubset<'a, T>> for multi::Subset<'a, T> {
+    fn from(s: CompressedSubset<'a, T>) -> Self {
+        multi::Subset {
+            set: s.set,
+            idxs: Cow::Owned(s.idxs.to_vec())
+        }
+    }
+}
+
+
+impl<'a, T> Index<usize> for CompressedSubset<'a, T> {
+    type Output = T;
+    fn index(&self, pos: usize) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a CompressedSubset<'a, T> {
+    type Item = &'a T;
+    type IntoIter = SubsetIter<'a, T>;
+    fn into_iter(self) -> SubsetIter<'a, T> {
+        self.iter()
+    }
+}
+
+
+/// Double-ended iterator over immutable references to selected items of set.
+#[derive(Debug,Clone)]
+pub struct SubsetIter<'a, T> {
+    set: &'a [T],
+    idxs: Iter<'a>
+}
+
+
+impl<'a, T> Iterator for SubsetIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.idxs.next().map(|idx| &self.set[idx])
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idxs.size_hint()
+    }
+}
+
+
+impl<'a, T> ExactSizeIterator for SubsetIter<'a, T> {}
+
+
+impl<'a, T> DoubleEndedIterator for SubsetIter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.idxs.next_back().map(|idx| &self.set[idx])
+    }
+}
+
+
+/// Subset of slice's items selected by compressed indexes
+/// that is able to iterate forward and backward over mutable or immutable references to selected items.
+/// Each item of a slice can be selected no more than once.
+pub struct CompressedSubsetMut<'a, T> {
+    pub(crate) set: &'a mut [T],
+    pub(crate) idxs: Cow<'a, CompressedIdxs>
+}
+
+
+impl<'a, T> CompressedSubsetMut<'a, T> {
+    /// Constructs a subset from the whole set and compressed indexes of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new(set: &'a mut [T], idxs: &'a CompressedIdxs) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        if idxs.max_len() > set.len() {
+            Err(SubsetError::OutOfBounds)
+        } else if !idxs.is_unique() {
+            Err(SubsetError::NotUnique)
+        } else { Ok(Self { set, idxs: Cow::Borrowed(idxs) }) }
+    }
+    /// Returns the original slice.
+    pub fn set(&mut self) -> &mut [T] {
+        self.set
+    }
+    /// Returns compressed indexes of selected items.
+    pub fn idxs(&self) -> &CompressedIdxs {
+        &self.idxs
+    }
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.idxs.len()
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.idxs.is_empty()
+    }
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        self.idxs.get(pos).map(|idx| &self.set[idx])
+    }
+    /// Returns a mutable reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        self.idxs.get(pos).map(move |idx| &mut self.set[idx])
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> SubsetIter<'_, T> {
+        SubsetIter {
+            set: self.set,
+            idxs: self.idxs.iter()
+        }
+    }
+    /// Returns an iterator over mutable references to selected items.
+    pub fn iter_mut(&mut self) -> SubsetIterMut<'_, T> {
+        SubsetIterMut {
+            ptr: self.set.as_mut_ptr(),
+            idxs: self.idxs.iter(),
+            _marker: PhantomData
+        }
+    }
+}
+
+
+impl<'a, T, I: Idx> From<unique::SubsetMut<'a, T, I>> for CompressedSubsetMut<'a, T> {
+    fn from(s: unique::SubsetMut<'a, T, I>) -> Self {
+        Self {
+            set: s.m.set,
+            idxs: Cow::Owned(s.m.idxs.iter().map(|idx| idx.index()).collect())
+        }
+    }
+}
+
+
+impl<'a, T> From<CompressedSubsetMut<'a, T>> for unique::SubsetMut<'a, T> {
+    fn from(s: CompressedSubsetMut<'a, T>) -> Self {
+        unique::SubsetMut {
+            m: multi::SubsetMut {
+                set: s.set,
+                idxs: Cow::Owned(s.idxs.to_vec())
+            }
+        }
+    }
+}
+
+
+impl<'a, T> Index<usize> for CompressedSubsetMut<'a, T> {
+    type Output = T;
+    fn index(&self, pos: usize) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IndexMut<usize> for CompressedSubsetMut<'a, T> {
+    fn index_mut(&mut self, pos: usize) -> &mut T {
+        self.get_mut(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a CompressedSubsetMut<'a, T> {
+    type Item = &'a T;
+    type IntoIter = SubsetIter<'a, T>;
+    fn into_iter(self) -> SubsetIter<'a, T> {
+        self.iter()
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a mut CompressedSubsetMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = SubsetIterMut<'a, T>;
+    fn into_iter(self) -> SubsetIterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+
+/// Double-ended iterator over mutable references to selected items of set.
+pub struct SubsetIterMut<'a, T> {
+    ptr: *mut T,    // Points to the set
+    idxs: Iter<'a>,
+    _marker: PhantomData<&'a mut T>
+}
+
+
+impl<'a, T: 'a> Iterator for SubsetIterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<&'a mut T> {
+        let idx = self.idxs.next()?;
+        Some(unsafe{&mut *self.ptr.add(idx)})
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idxs.size_hint()
+    }
+}
+
+
+impl<'a, T: 'a> ExactSizeIterator for SubsetIterMut<'a, T> {}
+
+
+impl<'a, T: 'a> DoubleEndedIterator for SubsetIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        let idx = self.idxs.next_back()?;
+        Some(unsafe{&mut *self.ptr.add(idx)})
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_idxs() {
+        let raw: Vec<usize> = (0..1000).map(|i| (i * 7919) % 1013).chain([usize::MAX - 1, 0, 1 << 40]).collect();
+        let idxs = CompressedIdxs::from(&raw[..]);
+        assert_eq!(idxs.len(), raw.len());
+        assert_eq!(idxs.to_vec(), raw);
+        assert_eq!(idxs.iter().rev().collect::<Vec<_>>(), raw.iter().rev().copied().collect::<Vec<_>>());
+        assert!((0..raw.len()).all(|pos| idxs.get(pos) == Some(raw[pos])));
+        assert_eq!(idxs.get(raw.len()), None);
+        let mut iter = idxs.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(raw[0]), Some(raw[raw.len() - 1])));
+        assert_eq!(iter.len(), raw.len() - 2);
+        assert_eq!(iter.count(), raw.len() - 2);
+        assert!(CompressedIdxs::new().iter().next_back().is_none());
+    }
+
+    #[test]
+    fn test_subset() {
+        let mut set = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let idxs: CompressedIdxs = [9, 2, 2].iter().copied().collect();
+        assert_eq!(CompressedSubsetMut::new(&mut set, &idxs).err(), Some(SubsetError::NotUnique));
+        let subset = CompressedSubset::new(&set, &idxs).unwrap();
+        assert!(!subset.is_unique());
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![9, 2, 2]);
+        let idxs: CompressedIdxs = [10].iter().copied().collect();
+        assert_eq!(CompressedSubset::new(&set, &idxs).err(), Some(SubsetError::OutOfBounds));
+        let idxs: CompressedIdxs = [7, 3, 5].iter().copied().collect();
+        let mut subset = CompressedSubsetMut::new(&mut set, &idxs).unwrap();
+        subset[1] = 30;
+        for v in subset.iter_mut().rev().take(2) {
+            *v *= 10;
+        }
+        assert_eq!(set, vec![0, 1, 2, 300, 4, 50, 6, 7, 8, 9]);
+        let subset: unique::SubsetMut<_> = CompressedSubsetMut::new(&mut set, &idxs).unwrap().into();
+        assert_eq!(subset.idxs(), &[7, 3, 5]);
+        let mut subset: CompressedSubsetMut<_> = subset.into();
+        subset[0] = 70;
+        assert_eq!(subset.idxs(), &idxs);
+        let subset: CompressedSubset<_> = multi::Subset::new(&set, &[0, 7, 0]).unwrap().into();
+        let subset: multi::Subset<_> = subset.into();
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![0, 70, 0]);
+    }
+}
@@ -0,0 +1,121 @@
+//! Extension trait for constructing subsets directly from slices and vectors.
+
+use super::{multi, unique, SubsetError};
+use std::borrow::Cow;
+
+
+/// Shorthand constructors of subsets, implemented for slices and vectors.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use subset::SubsetExt;
+/// 
+/// let mut data = vec![9, 8, 7, 6, 5, 4];
+/// let idxs = [1, 3];
+/// for v in data.subset_mut(&idxs).unwrap().iter_mut() {
+///     *v = 0;
+/// }
+/// assert_eq!(data.select_cloned(&[3, 0, 3]), vec![0, 9, 0]);
+/// assert_eq!(data.where_(|v| *v > 5).idxs(), &[0, 2]);
+/// ```
+pub trait SubsetExt<T> {
+    /// Constructs `unique::Subset` of selected items.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= self.len()`.
+    fn subset<'a>(&'a self, idxs: &'a [usize]) -> Result<unique::Subset<'a, T>, SubsetError>;
+    /// Constructs `unique::SubsetMut` of selected items.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= self.len()`.
+    fn subset_mut<'a>(&'a mut self, idxs: &'a [usize]) -> Result<unique::SubsetMut<'a, T>, SubsetError>;
+    /// Constructs `multi::Subset` of selected items.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= self.len()`.
+    fn multi_subset<'a>(&'a self, idxs: &'a [usize]) -> Result<multi::Subset<'a, T>, SubsetError>;
+    /// Clones selected items into a vector, in selection order.
+    /// Items may be selected more than once.
+    /// 
+    /// # Panics
+    /// Panics if any index is `>= self.len()`.
+    fn select_cloned(&self, idxs: &[usize]) -> Vec<T> where T: Clone;
+    /// Constructs `unique::Subset` of all items satisfying the predicate, in ascending index order.
+    /// The subset owns its indexes.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    fn where_<P: FnMut(&T) -> bool>(&self, predicate: P) -> unique::Subset<'_, T>;
+}
+
+
+impl<T> SubsetExt<T> for [T] {
+    fn subset<'a>(&'a self, idxs: &'a [usize]) -> Result<unique::Subset<'a, T>, SubsetError> {
+        unique::Subset::new(self, idxs)
+    }
+    fn subset_mut<'a>(&'a mut self, idxs: &'a [usize]) -> Result<unique::SubsetMut<'a, T>, SubsetError> {
+        unique::SubsetMut::new(self, idxs)
+    }
+    fn multi_subset<'a>(&'a self, idxs: &'a [usize]) -> Result<multi::Subset<'a, T>, SubsetError> {
+        multi::Subset::new(self, idxs)
+    }
+    fn select_cloned(&self, idxs: &[usize]) -> Vec<T> where T: Clone {
+        idxs.iter().map(|idx| self[*idx].clone()).collect()
+    }
+    fn where_<P: FnMut(&T) -> bool>(&self, mut predicate: P) -> unique::Subset<'_, T> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        let idxs = self.iter().enumerate()
+            .filter(|(_, v)| predicate(v))
+            .map(|(idx, _)| idx)
+            .collect();
+        unique::Subset {
+            m: multi::Subset {
+                set: self,
+                idxs: Cow::Owned(idxs)
+            }
+        }
+    }
+}
+
+
+impl<T> SubsetExt<T> for Vec<T> {
+    fn subset<'a>(&'a self, idxs: &'a [usize]) -> Result<unique::Subset<'a, T>, SubsetError> {
+        self.as_slice().subset(idxs)
+    }
+    fn subset_mut<'a>(&'a mut self, idxs: &'a [usize]) -> Result<unique::SubsetMut<'a, T>, SubsetError> {
+        self.as_mut_slice().subset_mut(idxs)
+    }
+    fn multi_subset<'a>(&'a self, idxs: &'a [usize]) -> Result<multi::Subset<'a, T>, SubsetError> {
+        self.as_slice().multi_subset(idxs)
+    }
+    fn select_cloned(&self, idxs: &[usize]) -> Vec<T> where T: Clone {
+        self.as_slice().select_cloned(idxs)
+    }
+    fn where_<P: FnMut(&T) -> bool>(&self, predicate: P) -> unique::Subset<'_, T> {
+        self.as_slice().where_(predicate)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_ext() {
+        let mut set = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        assert_eq!(set.subset(&[2, 2]).err(), Some(SubsetError::NotUnique));
+        assert_eq!(set.subset_mut(&[10]).err(), Some(SubsetError::OutOfBounds));
+        assert_eq!(set.multi_subset(&[2, 2]).unwrap().iter().sum::<i32>(), 14);
+        set[..].subset_mut(&[0, 9]).unwrap().iter_mut().for_each(|v| *v *= 10);
+        assert_eq!(set.select_cloned(&[9, 0, 9]), vec![0, 90, 0]);
+        let even = set.where_(|v| v % 2 == 0);
+        assert_eq!(even.idxs(), &[0, 1, 3, 5, 7, 9]);
+        let arr = [1, 2, 3];
+        assert_eq!(arr.subset(&[2, 0]).unwrap().iter().copied().collect::<Vec<_>>(), vec![3, 1]);
+    }
+}
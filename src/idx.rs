@@ -0,0 +1,105 @@
+//! Types of indexes of selected items.
+
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+
+/// Type of indexes of selected items, implemented for `u8`, `u16`, `u32`, `u64` and `usize`.
+/// 
+/// Narrow index types reduce the memory taken by index lists,
+/// while index newtypes keep index lists of different tables apart.
+/// 
+/// Only `multi` and `unique` subsets and their iterators, windows and cursors accept any `Idx` type.
+/// `FixedSubsetMut`, `ValidatedIdxs`, `UniquenessChecker`, `ZipSubsetMut`
+/// and the strided, range and compressed subsets take `usize` indexes only.
+/// 
+/// # Safety
+/// Uniqueness of selected items is checked with `Eq` and `Hash` of indexes,
+/// while items are accessed through `index()` without further checks. So implementations must ensure that
+/// `index()` returns the same position every time it is called on the same value,
+/// and that `a.index() == b.index()` if and only if `a == b`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use subset::{Idx, unique::SubsetMut};
+/// 
+/// #[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash)]
+/// struct EntityId(u32);
+/// 
+/// // `index()` is deterministic and injective, since `EntityId` compares by its only field
+/// unsafe impl Idx for EntityId {
+///     fn index(self) -> usize {
+///         self.0.index()
+///     }
+/// }
+/// 
+/// let mut health = [100, 80, 60];
+/// let hit = [EntityId(2), EntityId(0)];
+/// let mut subset = SubsetMut::new_typed(&mut health, &hit).unwrap();
+/// for v in subset.iter_mut() {
+///     *v -= 10;
+/// }
+/// assert_eq!(health, [90, 80, 50]);
+/// ```
+pub unsafe trait Idx: Copy + Ord + Hash + Debug {
+    /// Converts to a position in the set.
+    /// Values that do not fit in `usize` must saturate to `usize::MAX`, so that they are out of bounds of any set.
+    fn index(self) -> usize;
+}
+
+
+macro_rules! impl_idx {
+    ($($ty:ty),+) => {
+        $(unsafe impl Idx for $ty {
+            fn index(self) -> usize {
+                usize::try_from(self).unwrap_or(usize::MAX)
+            }
+        })+
+    };
+}
+
+impl_idx!(u8, u16, u32, u64);
+
+
+unsafe impl Idx for usize {
+    fn index(self) -> usize {
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{multi, unique, SubsetError, SubsetView};
+
+    #[test]
+    fn test_idx() {
+        assert_eq!(255u8.index(), 255);
+        assert_eq!(7u16.index(), 7);
+        assert_eq!(u32::MAX.index() as u64, u32::MAX as u64);
+        assert_eq!(usize::MAX.index(), usize::MAX);
+        assert_eq!(u64::MAX.index(), usize::MAX);
+    }
+
+    #[test]
+    fn test_typed_subsets() {
+        let mut set = vec![0, 1, 2, 3, 4];
+        let idxs: [u64; 2] = [1, u64::MAX];
+        assert_eq!(multi::Subset::new_typed(&set, &idxs).err(), Some(SubsetError::OutOfBounds));
+        let idxs: [u16; 3] = [4, 1, 4];
+        let subset = multi::Subset::new_typed(&set, &idxs).unwrap();
+        assert_eq!(subset.duplicates(), vec![4u16]);
+        assert_eq!(SubsetView::idxs(&subset).collect::<Vec<_>>(), vec![4, 1, 4]);
+        assert_eq!(unique::SubsetMut::new_typed(&mut set, &idxs).err(), Some(SubsetError::NotUnique));
+        let mut subset = unique::SubsetMut::from_typed_vec(&mut set, vec![3u8, 0]).unwrap();
+        for v in subset.iter_mut() {
+            *v += 10;
+        }
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![13, 10]);
+        assert_eq!(set, vec![10, 1, 2, 13, 4]);
+    }
+}
@@ -3,17 +3,6 @@
 
 // TODO: use bitvec
 
-// TODO: add compiletest
-// Example:
-//   let mut set = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
-//   let idxs = vec![2, 4, 7];
-//   let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
-//   let mut iter = subset.iter_mut();
-//   let r2 = iter.next().unwrap();
-//   subset.set()[0] = 100;   // MUST NOT COMPILE: Cannot borrow as mutable more than once at a time
-//   *r2 = 19;
-
-
 use std::collections::HashSet;
 
 /// Subset construction or conversion error.
@@ -23,11 +12,45 @@ pub enum SubsetError {
     OutOfBounds
 }
 
-fn is_unique(array: &[usize]) -> bool {
-    let mut uniques: HashSet<usize> = HashSet::with_capacity(array.len());
+/// What lazy subsets do with an index that is out of bounds or selected twice.
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum ErrorPolicy {
+    /// Yield the error and go on with the next index.
+    Yield,
+    /// Yield the error and stop.
+    Stop,
+    /// Skip the index silently.
+    Skip
+}
+
+fn is_unique<I: Idx>(array: &[I]) -> bool {
+    let mut uniques: HashSet<I> = HashSet::with_capacity(array.len());
     array.iter().all(|idx| uniques.insert(*idx))
 }
 
+fn is_unique_pairwise<I: Idx>(array: &[I]) -> bool {
+    array.iter().enumerate().all(|(i, idx)| !array[..i].contains(idx))
+}
+
 
 pub mod unique;
 pub mod multi;
+pub mod matrix;
+pub mod compressed;
+pub mod ranges;
+pub mod strided;
+#[cfg(feature = "ndarray")]
+pub mod nd;
+mod view;
+mod cmp;
+#[cfg(feature = "serde")]
+mod serialization;
+mod ext;
+mod idx;
+pub use view::{ArrayWindows, SubsetView, SubsetViewMut, Window, WindowIdxs, WindowIter, Windows};
+pub use ext::SubsetExt;
+pub use idx::Idx;
+#[cfg(feature = "serde")]
+pub use serialization::PatchSeed;
+#[doc(hidden)]
+pub mod macros;
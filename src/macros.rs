@@ -0,0 +1,208 @@
+//! Support code for `subset!` and `subset_mut!` macros.
+
+use super::{multi, unique, SubsetError};
+
+
+/// Constructs `unique::Subset` from a set and a list of indexes.
+/// 
+/// If all indexes are literals, duplicates are rejected at compile time,
+/// as well as out-of-bounds indexes if the set is an array.
+/// Otherwise it falls back to the runtime checks of `unique::Subset::new`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use subset::subset;
+/// 
+/// let set = [9, 8, 7, 6, 5];
+/// let subset = subset![set; 1, 3].unwrap();
+/// assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![8, 6]);
+/// let i = 1;
+/// assert!(subset![set; i, 1].is_err());
+/// ```
+/// 
+/// Duplicate literal indexes do not compile:
+/// 
+/// ```compile_fail
+/// # use subset::subset;
+/// let set = vec![9, 8, 7, 6, 5];
+/// let subset = subset![set; 1, 3, 1];
+/// ```
+/// 
+/// Neither do out-of-bounds literal indexes of an array:
+/// 
+/// ```compile_fail
+/// # use subset::subset;
+/// let set = [9, 8, 7, 6, 5];
+/// let subset = subset![set; 1, 5];
+/// ```
+#[macro_export]
+macro_rules! subset {
+    ($set:expr; $($idx:literal),+ $(,)?) => {
+        // `$set` is evaluated once
+        match $set {
+            ref s => {
+                const IDXS: &[usize] = &[$($idx),+];
+                const _: () = assert!($crate::macros::is_unique(IDXS), "duplicate indexes in subset!");
+                const MAX: usize = $crate::macros::max(IDXS);
+                #[allow(unused_imports)]
+                use $crate::macros::RuntimeBounds as _;
+                $crate::macros::ArrayBounds::<_, MAX>(s).check_bounds();
+                // Uniqueness is asserted at compile time above
+                unsafe{$crate::macros::literal_subset(&s[..], IDXS)}
+            }
+        }
+    };
+    ($set:expr; $($idx:expr),+ $(,)?) => {
+        $crate::unique::Subset::from_vec(&$set[..], vec![$($idx),+])
+    };
+}
+
+
+/// Constructs `unique::SubsetMut` from a set and a list of indexes.
+/// 
+/// If all indexes are literals, duplicates are rejected at compile time,
+/// as well as out-of-bounds indexes if the set is an array.
+/// Otherwise it falls back to the runtime checks of `unique::SubsetMut::new`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use subset::subset_mut;
+/// 
+/// let mut set = vec![9, 8, 7, 6, 5];
+/// let mut subset = subset_mut![set; 2, 4].unwrap();
+/// for v in subset.iter_mut() {
+///     *v = 0;
+/// }
+/// assert_eq!(set, vec![9, 8, 0, 6, 0]);
+/// assert!(subset_mut![set; 2, 5].is_err());
+/// ```
+/// 
+/// Duplicate literal indexes do not compile:
+/// 
+/// ```compile_fail
+/// # use subset::subset_mut;
+/// let mut set = vec![9, 8, 7, 6, 5];
+/// let subset = subset_mut![set; 2, 4, 2];
+/// ```
+/// 
+/// Neither do out-of-bounds literal indexes of an array:
+/// 
+/// ```compile_fail
+/// # use subset::subset_mut;
+/// let mut set = [9, 8, 7, 6, 5];
+/// let subset = subset_mut![set; 7];
+/// ```
+#[macro_export]
+macro_rules! subset_mut {
+    ($set:expr; $($idx:literal),+ $(,)?) => {
+        // `$set` is evaluated once
+        match $set {
+            ref mut s => {
+                const IDXS: &[usize] = &[$($idx),+];
+                const _: () = assert!($crate::macros::is_unique(IDXS), "duplicate indexes in subset_mut!");
+                const MAX: usize = $crate::macros::max(IDXS);
+                #[allow(unused_imports)]
+                use $crate::macros::RuntimeBounds as _;
+                $crate::macros::ArrayBounds::<_, MAX>(&*s).check_bounds();
+                // Uniqueness is asserted at compile time above
+                unsafe{$crate::macros::literal_subset_mut(&mut s[..], IDXS)}
+            }
+        }
+    };
+    ($set:expr; $($idx:expr),+ $(,)?) => {
+        $crate::unique::SubsetMut::from_vec(&mut $set[..], vec![$($idx),+])
+    };
+}
+
+
+pub const fn is_unique(idxs: &[usize]) -> bool {
+    let mut i = 0;
+    while i < idxs.len() {
+        let mut j = 0;
+        while j < i {
+            if idxs[i] == idxs[j] {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+
+pub const fn max(idxs: &[usize]) -> usize {
+    let mut result = 0;
+    let mut i = 0;
+    while i < idxs.len() {
+        if idxs[i] > result {
+            result = idxs[i];
+        }
+        i += 1;
+    }
+    result
+}
+
+
+// Inherent method takes precedence over the trait one, so arrays get the compile-time bounds check
+// and everything else is checked at runtime.
+pub struct ArrayBounds<'s, S: ?Sized, const MAX: usize>(pub &'s S);
+
+struct Assert<const LEN: usize, const MAX: usize>;
+
+impl<const LEN: usize, const MAX: usize> Assert<LEN, MAX> {
+    const IN_BOUNDS: () = assert!(MAX < LEN, "index out of bounds of the array");
+}
+
+impl<T, const LEN: usize, const MAX: usize> ArrayBounds<'_, [T; LEN], MAX> {
+    #[inline(always)]
+    pub fn check_bounds(&self) {
+        #[allow(clippy::let_unit_value)]
+        let () = Assert::<LEN, MAX>::IN_BOUNDS;
+    }
+}
+
+pub trait RuntimeBounds {
+    #[inline(always)]
+    fn check_bounds(&self) {}
+}
+
+impl<S: ?Sized, const MAX: usize> RuntimeBounds for ArrayBounds<'_, S, MAX> {}
+
+
+/// Constructs a unique subset checking only the bounds.
+/// 
+/// # Safety
+/// No index may occur twice.
+pub unsafe fn literal_subset<'a, T>(set: &'a [T], idxs: &'static [usize]) -> Result<unique::Subset<'a, T>, SubsetError> {
+    Ok(multi::Subset::new(set, idxs)?.to_unique_unchecked())
+}
+
+
+/// Constructs a mutable unique subset checking only the bounds.
+/// 
+/// # Safety
+/// No index may occur twice.
+pub unsafe fn literal_subset_mut<'a, T>(set: &'a mut [T], idxs: &'static [usize]) -> Result<unique::SubsetMut<'a, T>, SubsetError> {
+    Ok(multi::SubsetMut::new(set, idxs)?.to_unique_mut_unchecked())
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::Cell;
+
+    #[test]
+    fn test_macros() {
+        let mut set = [0, 1, 2];
+        let calls = Cell::new(0);
+        let mut subset = subset_mut![*{ calls.set(calls.get() + 1); &mut set }; 2, 0].unwrap();
+        subset.iter_mut().for_each(|v| *v += 10);
+        let subset = subset![*{ calls.set(calls.get() + 1); &set }; 1, 2].unwrap();
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![1, 12]);
+        assert_eq!(calls.get(), 2);
+    }
+}
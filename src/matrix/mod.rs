@@ -0,0 +1,480 @@
+//! Row, column and `ix_`-style selections on row-major matrices stored in a slice.
+//! Both row and column indexes follow the rules of `unique::SubsetMut`: each one is checked against bounds and for uniqueness.
+//! 
+//! Every view holds either `&[T]` or `&mut [T]`, see `MatrixData`; mutable access is available only in the latter case.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use subset::matrix::*;
+//! 
+//! // 3x4 matrix
+//! let mut data = [ 0,  1,  2,  3,
+//!                 10, 11, 12, 13,
+//!                 20, 21, 22, 23];
+//! let rows = [2, 0];
+//! let cols = [1, 3];
+//! let sub = Submatrix::new(&data[..], 4, &rows, &cols).unwrap();
+//! assert_eq!(sub.gather(), vec![21, 23, 1, 3]);
+//! 
+//! let mut sub = Submatrix::new(&mut data[..], 4, &rows, &cols).unwrap();
+//! for mut row in sub.iter_mut() {
+//!     for v in row.iter_mut() {
+//!         *v = -*v;
+//!     }
+//! }
+//! assert_eq!(data, [ 0, -1,  2, -3,
+//!                   10, 11, 12, 13,
+//!                   20, -21, 22, -23]);
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use super::{is_unique, multi, unique, SubsetError};
+
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+
+/// Storage of a matrix, implemented only for `&[T]` and `&mut [T]`.
+/// 
+/// Views check the shape once on construction and access items without bounds checks afterwards,
+/// which is sound only because these implementations of `Deref` always return the same slice.
+pub trait MatrixData: sealed::Sealed {}
+
+impl<T> sealed::Sealed for &[T] {}
+impl<T> sealed::Sealed for &mut [T] {}
+impl<T> MatrixData for &[T] {}
+impl<T> MatrixData for &mut [T] {}
+
+
+fn check_idxs(idxs: &[usize], len: usize) -> Result<(), SubsetError> {
+    if idxs.iter().any(|v| *v >= len) {
+        Err(SubsetError::OutOfBounds)
+    } else if !is_unique(idxs) {
+        Err(SubsetError::NotUnique)
+    } else { Ok(()) }
+}
+
+
+// Returns the number of rows
+fn check_shape<T>(data: &[T], stride: usize) -> usize {
+    assert_ne!(std::mem::size_of::<T>(), 0);
+    assert_ne!(stride, 0);
+    assert_eq!(data.len() % stride, 0);
+    data.len() / stride
+}
+
+
+/// Double-ended iterator over mutable slices of selected rows.
+pub struct RowsMut<'s, T> {
+    ptr: *mut T,    // Points to the data
+    stride: usize,
+    iter: std::slice::Iter<'s, usize>,
+    _marker: PhantomData<&'s mut T>
+}
+
+
+impl<'s, T> RowsMut<'s, T> {
+    // Safety: rows are unique and in bounds of data
+    unsafe fn new(data: &'s mut [T], stride: usize, rows: &'s [usize]) -> Self {
+        Self {
+            ptr: data.as_mut_ptr(),
+            stride,
+            iter: rows.iter(),
+            _marker: PhantomData
+        }
+    }
+}
+
+
+impl<'s, T: 's> Iterator for RowsMut<'s, T> {
+    type Item = &'s mut [T];
+    fn next(&mut self) -> Option<&'s mut [T]> {
+        let row = self.iter.next()?;
+        Some(unsafe{std::slice::from_raw_parts_mut(self.ptr.add(row * self.stride), self.stride)})
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<'s, T: 's> ExactSizeIterator for RowsMut<'s, T> {}
+
+
+impl<'s, T: 's> DoubleEndedIterator for RowsMut<'s, T> {
+    fn next_back(&mut self) -> Option<&'s mut [T]> {
+        let row = self.iter.next_back()?;
+        Some(unsafe{std::slice::from_raw_parts_mut(self.ptr.add(row * self.stride), self.stride)})
+    }
+}
+
+
+/// Selected rows of a row-major matrix.
+pub struct RowSubset<'a, D> {
+    data: D,
+    stride: usize,
+    rows: &'a [usize]
+}
+
+
+impl<'a, T: 'a, D: Deref<Target = [T]> + MatrixData> RowSubset<'a, D> {
+    /// Constructs a row subset of the matrix stored in `data` with `stride` items per row.
+    /// Both the uniqueness of the selected rows and the bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate row indexes.
+    /// OutOfBounds, if any row index is `>= data.len() / stride`.
+    /// 
+    /// # Panics
+    /// Panics if `stride == 0`, `data.len() % stride != 0` or `std::mem::size_of::<T>() == 0`
+    pub fn new(data: D, stride: usize, rows: &'a [usize]) -> Result<Self, SubsetError> {
+        check_idxs(rows, check_shape(&data, stride))?;
+        Ok(Self { data, stride, rows })
+    }
+    /// Returns the whole matrix.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+    /// Returns the number of items per row.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+    /// Returns indexes of selected rows.
+    pub fn row_idxs(&self) -> &[usize] {
+        self.rows
+    }
+    /// Returns the `pos`-th selected row or `None` if `pos >= row_idxs().len()`.
+    pub fn row(&self, pos: usize) -> Option<&[T]> {
+        let row = *self.rows.get(pos)?;
+        Some(&self.data[row * self.stride..(row + 1) * self.stride])
+    }
+    /// Returns an iterator over selected rows.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + '_ {
+        self.rows.iter().map(move |row| &self.data[row * self.stride..(row + 1) * self.stride])
+    }
+    /// Returns a strided iterator over items of column `col` in selected rows.
+    /// 
+    /// # Panics
+    /// Panics if `col >= stride()`
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        assert!(col < self.stride);
+        self.rows.iter().map(move |row| &self.data[row * self.stride + col])
+    }
+    /// Clones selected rows into a dense row-major matrix.
+    pub fn gather(&self) -> Vec<T> where T: Clone {
+        let mut dense = Vec::with_capacity(self.rows.len() * self.stride);
+        self.iter().for_each(|row| dense.extend_from_slice(row));
+        dense
+    }
+    /// Returns an iterator over all contiguous windows of `n` selected rows, as row subsets.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> impl DoubleEndedIterator<Item = RowSubset<'_, &[T]>> + ExactSizeIterator + '_ {
+        self.rows.windows(n).map(move |rows| RowSubset { data: &*self.data, stride: self.stride, rows })
+    }
+}
+
+
+impl<'a, T: 'a, D: DerefMut<Target = [T]> + MatrixData> RowSubset<'a, D> {
+    /// Returns the `pos`-th selected row or `None` if `pos >= row_idxs().len()`.
+    pub fn row_mut(&mut self, pos: usize) -> Option<&mut [T]> {
+        let row = *self.rows.get(pos)?;
+        Some(&mut self.data[row * self.stride..(row + 1) * self.stride])
+    }
+    /// Returns an iterator over mutable selected rows.
+    pub fn iter_mut(&mut self) -> RowsMut<'_, T> {
+        unsafe{RowsMut::new(&mut self.data, self.stride, self.rows)}
+    }
+    /// Returns a strided iterator over mutable items of column `col` in selected rows.
+    /// 
+    /// # Panics
+    /// Panics if `col >= stride()`
+    pub fn col_mut(&mut self, col: usize) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator + '_ {
+        assert!(col < self.stride);
+        self.iter_mut().map(move |row| &mut row[col])
+    }
+    /// Assigns clones of a dense row-major matrix to selected rows.
+    /// 
+    /// # Panics
+    /// Panics if `dense.len() != row_idxs().len() * stride()`
+    pub fn scatter(&mut self, dense: &[T]) where T: Clone {
+        assert_eq!(dense.len(), self.rows.len() * self.stride);
+        let stride = self.stride;
+        self.iter_mut().zip(dense.chunks(stride)).for_each(|(row, values)| row.clone_from_slice(values));
+    }
+}
+
+
+/// Selected columns of a row-major matrix.
+pub struct ColSubset<'a, D> {
+    data: D,
+    stride: usize,
+    cols: &'a [usize]
+}
+
+
+impl<'a, T: 'a, D: Deref<Target = [T]> + MatrixData> ColSubset<'a, D> {
+    /// Constructs a column subset of the matrix stored in `data` with `stride` items per row.
+    /// Both the uniqueness of the selected columns and the bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate column indexes.
+    /// OutOfBounds, if any column index is `>= stride`.
+    /// 
+    /// # Panics
+    /// Panics if `stride == 0`, `data.len() % stride != 0` or `std::mem::size_of::<T>() == 0`
+    pub fn new(data: D, stride: usize, cols: &'a [usize]) -> Result<Self, SubsetError> {
+        check_shape(&data, stride);
+        check_idxs(cols, stride)?;
+        Ok(Self { data, stride, cols })
+    }
+    /// Returns the whole matrix.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+    /// Returns the number of items per row.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+    /// Returns indexes of selected columns.
+    pub fn col_idxs(&self) -> &[usize] {
+        self.cols
+    }
+    /// Returns an iterator over rows, each being a subset of selected columns.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = unique::Subset<'_, T>> + ExactSizeIterator + '_ {
+        let cols = self.cols;
+        self.data.chunks(self.stride).map(move |row| unsafe{multi::Subset::new_unchecked(row, cols).to_unique_unchecked()})
+    }
+    /// Returns a strided iterator over items of the `pos`-th selected column.
+    /// 
+    /// # Panics
+    /// Panics if `pos >= col_idxs().len()`
+    pub fn col(&self, pos: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        let col = self.cols[pos];
+        self.data.chunks(self.stride).map(move |row| &row[col])
+    }
+    /// Clones selected columns into a dense row-major matrix.
+    pub fn gather(&self) -> Vec<T> where T: Clone {
+        let mut dense = Vec::with_capacity(self.data.len() / self.stride * self.cols.len());
+        self.iter().for_each(|row| dense.extend(row.iter().cloned()));
+        dense
+    }
+    /// Returns an iterator over all contiguous windows of `n` selected columns, as column subsets.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> impl DoubleEndedIterator<Item = ColSubset<'_, &[T]>> + ExactSizeIterator + '_ {
+        self.cols.windows(n).map(move |cols| ColSubset { data: &*self.data, stride: self.stride, cols })
+    }
+}
+
+
+impl<'a, T: 'a, D: DerefMut<Target = [T]> + MatrixData> ColSubset<'a, D> {
+    /// Returns an iterator over rows, each being a mutable subset of selected columns.
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = unique::SubsetMut<'_, T>> + ExactSizeIterator + '_ {
+        let cols = self.cols;
+        self.data.chunks_mut(self.stride).map(move |row| unsafe{multi::SubsetMut::new_unchecked(row, cols).to_unique_mut_unchecked()})
+    }
+    /// Returns a strided iterator over mutable items of the `pos`-th selected column.
+    /// 
+    /// # Panics
+    /// Panics if `pos >= col_idxs().len()`
+    pub fn col_mut(&mut self, pos: usize) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator + '_ {
+        let col = self.cols[pos];
+        self.data.chunks_mut(self.stride).map(move |row| &mut row[col])
+    }
+    /// Assigns clones of a dense row-major matrix to selected columns.
+    /// 
+    /// # Panics
+    /// Panics if `dense.len() != data().len() / stride() * col_idxs().len()`
+    pub fn scatter(&mut self, dense: &[T]) where T: Clone {
+        let width = self.cols.len();
+        assert_eq!(dense.len(), self.data.len() / self.stride * width);
+        if width == 0 {
+            return;
+        }
+        for (mut row, values) in self.iter_mut().zip(dense.chunks(width)) {
+            row.iter_mut().zip(values).for_each(|(v, value)| v.clone_from(value));
+        }
+    }
+}
+
+
+/// Intersection of selected rows and selected columns of a row-major matrix, like numpy's `ix_`.
+pub struct Submatrix<'a, D> {
+    data: D,
+    stride: usize,
+    rows: &'a [usize],
+    cols: &'a [usize]
+}
+
+
+impl<'a, T: 'a, D: Deref<Target = [T]> + MatrixData> Submatrix<'a, D> {
+    /// Constructs a submatrix of the matrix stored in `data` with `stride` items per row.
+    /// Both the uniqueness of the selected rows and columns and the bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate row or column indexes.
+    /// OutOfBounds, if any row index is `>= data.len() / stride` or any column index is `>= stride`.
+    /// 
+    /// # Panics
+    /// Panics if `stride == 0`, `data.len() % stride != 0` or `std::mem::size_of::<T>() == 0`
+    pub fn new(data: D, stride: usize, rows: &'a [usize], cols: &'a [usize]) -> Result<Self, SubsetError> {
+        check_idxs(rows, check_shape(&data, stride))?;
+        check_idxs(cols, stride)?;
+        Ok(Self { data, stride, rows, cols })
+    }
+    /// Returns the whole matrix.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+    /// Returns the number of items per row.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+    /// Returns indexes of selected rows.
+    pub fn row_idxs(&self) -> &[usize] {
+        self.rows
+    }
+    /// Returns indexes of selected columns.
+    pub fn col_idxs(&self) -> &[usize] {
+        self.cols
+    }
+    /// Returns the item in the `row_pos`-th selected row and the `col_pos`-th selected column.
+    pub fn get(&self, row_pos: usize, col_pos: usize) -> Option<&T> {
+        let (row, col) = (*self.rows.get(row_pos)?, *self.cols.get(col_pos)?);
+        Some(&self.data[row * self.stride + col])
+    }
+    /// Returns an iterator over selected rows, each being a subset of selected columns.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = unique::Subset<'_, T>> + ExactSizeIterator + '_ {
+        self.rows.iter().map(move |row| {
+            let row = &self.data[row * self.stride..(row + 1) * self.stride];
+            unsafe{multi::Subset::new_unchecked(row, self.cols).to_unique_unchecked()}
+        })
+    }
+    /// Returns a strided iterator over items of the `pos`-th selected column in selected rows.
+    /// 
+    /// # Panics
+    /// Panics if `pos >= col_idxs().len()`
+    pub fn col(&self, pos: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        let col = self.cols[pos];
+        self.rows.iter().map(move |row| &self.data[row * self.stride + col])
+    }
+    /// Clones the submatrix into a dense row-major matrix.
+    pub fn gather(&self) -> Vec<T> where T: Clone {
+        let mut dense = Vec::with_capacity(self.rows.len() * self.cols.len());
+        self.iter().for_each(|row| dense.extend(row.iter().cloned()));
+        dense
+    }
+    /// Returns an iterator over all contiguous windows of `n` selected rows, as submatrices of all selected columns.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> impl DoubleEndedIterator<Item = Submatrix<'_, &[T]>> + ExactSizeIterator + '_ {
+        self.rows.windows(n).map(move |rows| Submatrix { data: &*self.data, stride: self.stride, rows, cols: self.cols })
+    }
+}
+
+
+impl<'a, T: 'a, D: DerefMut<Target = [T]> + MatrixData> Submatrix<'a, D> {
+    /// Returns the item in the `row_pos`-th selected row and the `col_pos`-th selected column.
+    pub fn get_mut(&mut self, row_pos: usize, col_pos: usize) -> Option<&mut T> {
+        let (row, col) = (*self.rows.get(row_pos)?, *self.cols.get(col_pos)?);
+        Some(&mut self.data[row * self.stride + col])
+    }
+    /// Returns an iterator over selected rows, each being a mutable subset of selected columns.
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = unique::SubsetMut<'_, T>> + ExactSizeIterator + '_ {
+        let cols = self.cols;
+        unsafe{RowsMut::new(&mut self.data, self.stride, self.rows)}
+            .map(move |row| unsafe{multi::SubsetMut::new_unchecked(row, cols).to_unique_mut_unchecked()})
+    }
+    /// Returns a strided iterator over mutable items of the `pos`-th selected column in selected rows.
+    /// 
+    /// # Panics
+    /// Panics if `pos >= col_idxs().len()`
+    pub fn col_mut(&mut self, pos: usize) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator + '_ {
+        let col = self.cols[pos];
+        unsafe{RowsMut::new(&mut self.data, self.stride, self.rows)}.map(move |row| &mut row[col])
+    }
+    /// Assigns clones of a dense row-major matrix to the submatrix.
+    /// 
+    /// # Panics
+    /// Panics if `dense.len() != row_idxs().len() * col_idxs().len()`
+    pub fn scatter(&mut self, dense: &[T]) where T: Clone {
+        let width = self.cols.len();
+        assert_eq!(dense.len(), self.rows.len() * width);
+        if width == 0 {
+            return;
+        }
+        for (mut row, values) in self.iter_mut().zip(dense.chunks(width)) {
+            row.iter_mut().zip(values).for_each(|(v, value)| v.clone_from(value));
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_rows() {
+        let mut data: Vec<i32> = (0..12).collect();
+        assert_eq!(RowSubset::new(&data[..], 4, &[1, 1]).err(), Some(SubsetError::NotUnique));
+        assert_eq!(RowSubset::new(&data[..], 4, &[3]).err(), Some(SubsetError::OutOfBounds));
+        let rows = [2, 0];
+        let mut sub = RowSubset::new(&mut data[..], 4, &rows).unwrap();
+        assert_eq!(sub.row(0), Some(&[8, 9, 10, 11][..]));
+        assert_eq!(sub.gather(), vec![8, 9, 10, 11, 0, 1, 2, 3]);
+        assert_eq!(sub.col(1).copied().collect::<Vec<_>>(), vec![9, 1]);
+        assert_eq!(sub.windows(2).map(|w| w.row_idxs().to_vec()).collect::<Vec<_>>(), vec![vec![2, 0]]);
+        sub.col_mut(3).for_each(|v| *v = -1);
+        let mut rows_mut = sub.iter_mut();
+        let (a, b) = (rows_mut.next().unwrap(), rows_mut.next().unwrap());
+        a.swap_with_slice(b);
+        sub.row_mut(1).unwrap()[0] = 100;
+        assert_eq!(data, vec![100, 9, 10, -1, 4, 5, 6, 7, 0, 1, 2, -1]);
+        let mut sub = RowSubset::new(&mut data[..], 4, &rows).unwrap();
+        sub.scatter(&[0; 8]);
+        assert_eq!(data, vec![0, 0, 0, 0, 4, 5, 6, 7, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_cols() {
+        let mut data: Vec<i32> = (0..12).collect();
+        assert_eq!(ColSubset::new(&data[..], 4, &[1, 1]).err(), Some(SubsetError::NotUnique));
+        assert_eq!(ColSubset::new(&data[..], 4, &[4]).err(), Some(SubsetError::OutOfBounds));
+        let cols = [3, 1];
+        let mut sub = ColSubset::new(&mut data[..], 4, &cols).unwrap();
+        assert_eq!(sub.gather(), vec![3, 1, 7, 5, 11, 9]);
+        assert_eq!(sub.col(1).copied().collect::<Vec<_>>(), vec![1, 5, 9]);
+        assert_eq!(sub.windows(1).next_back().unwrap().gather(), vec![1, 5, 9]);
+        sub.col_mut(0).for_each(|v| *v = 0);
+        sub.scatter(&sub.gather().iter().map(|v| v * 10).collect::<Vec<_>>());
+        assert_eq!(data, vec![0, 10, 2, 0, 4, 50, 6, 0, 8, 90, 10, 0]);
+    }
+
+    #[test]
+    fn test_submatrix() {
+        let mut data: Vec<i32> = (0..12).collect();
+        assert_eq!(Submatrix::new(&data[..], 4, &[1], &[0, 0]).err(), Some(SubsetError::NotUnique));
+        assert_eq!(Submatrix::new(&data[..], 4, &[3], &[0]).err(), Some(SubsetError::OutOfBounds));
+        let (rows, cols) = ([1, 2], [0, 3]);
+        let mut sub = Submatrix::new(&mut data[..], 4, &rows, &cols).unwrap();
+        assert_eq!(sub.get(1, 1), Some(&11));
+        assert_eq!(sub.get(2, 1), None);
+        assert_eq!(sub.col(0).copied().collect::<Vec<_>>(), vec![4, 8]);
+        *sub.get_mut(0, 0).unwrap() = 40;
+        sub.col_mut(1).rev().for_each(|v| *v += 100);
+        assert_eq!(sub.gather(), vec![40, 107, 8, 111]);
+        assert_eq!(sub.windows(1).map(|w| w.gather()).collect::<Vec<_>>(), vec![vec![40, 107], vec![8, 111]]);
+        sub.scatter(&[-1, -2, -3, -4]);
+        assert_eq!(data, vec![0, 1, 2, 3, -1, 5, 6, -2, -3, 9, 10, -4]);
+    }
+}
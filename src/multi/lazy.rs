@@ -0,0 +1,127 @@
+//! Multi-subsets over index iterators that are checked while iterating.
+
+use super::{ErrorPolicy, SubsetError};
+
+
+/// Multi-subset of slice's items selected by an iterator over indexes, without collecting them.
+/// Array bounds is checked for every index when it is reached.
+/// Each item of a slice can be selected more than once.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::multi::*;
+/// let set = [10, 20, 30];
+/// let subset = LazySubset::new(&set, [2, 5, 2, 0].iter().copied());
+/// assert_eq!(subset.collect::<Vec<_>>(), vec![Ok(&30), Err(SubsetError::OutOfBounds), Ok(&30), Ok(&10)]);
+/// 
+/// let subset = LazySubset::with_policy(&set, (0..).step_by(2), ErrorPolicy::Stop);
+/// assert_eq!(subset.filter_map(Result::ok).sum::<i32>(), 40);
+/// ```
+pub struct LazySubset<'a, T, I> {
+    set: &'a [T],
+    idxs: I,
+    policy: ErrorPolicy,
+    stopped: bool
+}
+
+
+impl<'a, T, I: Iterator<Item = usize>> LazySubset<'a, T, I> {
+    /// Constructs a multi-subset from the whole set and an iterator over indexes of the selected items.
+    /// Indexes out of bounds yield errors and are skipped.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new<J: IntoIterator<IntoIter = I>>(set: &'a [T], idxs: J) -> Self {
+        Self::with_policy(set, idxs, ErrorPolicy::Yield)
+    }
+    /// Constructs a multi-subset from the whole set and an iterator over indexes of the selected items
+    /// that treats indexes out of bounds according to `policy`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn with_policy<J: IntoIterator<IntoIter = I>>(set: &'a [T], idxs: J, policy: ErrorPolicy) -> Self {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        Self {
+            set,
+            idxs: idxs.into_iter(),
+            policy,
+            stopped: false
+        }
+    }
+    /// Returns the original slice.
+    pub fn set(&self) -> &[T] {
+        self.set
+    }
+    /// Returns the policy for indexes out of bounds.
+    pub fn policy(&self) -> ErrorPolicy {
+        self.policy
+    }
+}
+
+
+impl<'a, T, I: Clone> Clone for LazySubset<'a, T, I> {
+    fn clone(&self) -> Self {
+        Self {
+            set: self.set,
+            idxs: self.idxs.clone(),
+            policy: self.policy,
+            stopped: self.stopped
+        }
+    }
+}
+
+
+impl<'a, T, I: Iterator<Item = usize>> Iterator for LazySubset<'a, T, I> {
+    type Item = Result<&'a T, SubsetError>;
+    fn next(&mut self) -> Option<Result<&'a T, SubsetError>> {
+        if self.stopped {
+            return None;
+        }
+        loop {
+            let idx = self.idxs.next()?;
+            match self.set.get(idx) {
+                Some(v) => return Some(Ok(v)),
+                None => match self.policy {
+                    ErrorPolicy::Yield => return Some(Err(SubsetError::OutOfBounds)),
+                    ErrorPolicy::Stop => {
+                        self.stopped = true;
+                        return Some(Err(SubsetError::OutOfBounds));
+                    }
+                    ErrorPolicy::Skip => continue
+                }
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.stopped {
+            return (0, Some(0));
+        }
+        match self.policy {
+            ErrorPolicy::Skip => (0, self.idxs.size_hint().1),
+            _ => self.idxs.size_hint()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_lazy() {
+        let set = [1, 2, 3];
+        let idxs = [0, 3, 1, 4, 2];
+        let yielded: Vec<_> = LazySubset::new(&set, idxs.iter().copied()).collect();
+        assert_eq!(yielded, vec![Ok(&1), Err(SubsetError::OutOfBounds), Ok(&2), Err(SubsetError::OutOfBounds), Ok(&3)]);
+        let stopped: Vec<_> = LazySubset::with_policy(&set, idxs.iter().copied(), ErrorPolicy::Stop).collect();
+        assert_eq!(stopped, vec![Ok(&1), Err(SubsetError::OutOfBounds)]);
+        let skipped = LazySubset::with_policy(&set, idxs.iter().copied(), ErrorPolicy::Skip);
+        assert_eq!(skipped.size_hint(), (0, Some(5)));
+        assert_eq!(skipped.map(Result::unwrap).collect::<Vec<_>>(), vec![&1, &2, &3]);
+        let repeated = LazySubset::new(&set, std::iter::repeat_n(2, 3));
+        assert_eq!(repeated.filter_map(Result::ok).sum::<i32>(), 9);
+    }
+}
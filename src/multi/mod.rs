@@ -53,25 +53,31 @@
 //! ```
 
 pub use std::convert::{From,Into,TryFrom,TryInto};
-use super::{is_unique, unique};
-pub use super::SubsetError;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use super::{is_unique, unique, Idx};
+pub use super::{ErrorPolicy, SubsetError};
+
+mod lazy;
+mod windows;
+pub use lazy::*;
+pub use windows::*;
 
 
 /// Multi-subset of slice's items that is able to iterate forward and backward over references to selected items.
 /// Each item of a slice can be selected more than once.
 /// 
 /// The only difference between Subset and SubsetMut is that Subset holds immutable reference to original set.
-#[derive(Debug)]
-pub struct Subset<'a, T> {
+pub struct Subset<'a, T, I: Idx = usize> {
     pub(crate) set: &'a [T],
-    pub(crate) idxs: &'a [usize]
+    pub(crate) idxs: Cow<'a, [I]>
 }
 
 
 /// Double-ended iterator over immutable references to selected items of set.
-pub struct Iter<'a, T> {
-    ptr: *const T,    // Points to the set
-    iter: std::slice::Iter<'a, usize>
+pub struct Iter<'a, T, I = usize> {
+    pub(crate) ptr: *const T,    // Points to the set
+    pub(crate) iter: std::slice::Iter<'a, I>
 }
 
 
@@ -95,18 +101,76 @@ impl<'a, T> Subset<'a, T> {
     /// # Panics
     /// Panics if `std::mem::size_of::<T>() == 0`
     pub fn new(set: &'a [T], idxs: &'a [usize]) -> Result<Self, SubsetError> {
+        Self::new_typed(set, idxs)
+    }
+    /// Constructs a multi-subset from the whole set and indexes of the selected items.
+    /// No array bounds check.
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()`.
+    pub unsafe fn new_unchecked(set: &'a [T], idxs: &'a [usize]) -> Self {
+        Self::new_typed_unchecked(set, idxs)
+    }
+    /// Constructs a multi-subset that owns indexes of the selected items.
+    /// Array bounds is checked.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_vec(set: &'a [T], idxs: Vec<usize>) -> Result<Self, SubsetError> {
+        Self::from_typed_vec(set, idxs)
+    }
+}
+
+
+impl<'a, T, I: Idx> Subset<'a, T, I> {
+    /// Constructs a multi-subset from the whole set and indexes of any `Idx` type of the selected items.
+    /// Array bounds is checked.
+    /// Note that subsets are not designed for ZSTs.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new_typed(set: &'a [T], idxs: &'a [I]) -> Result<Self, SubsetError> {
         assert_ne!(std::mem::size_of::<T>(), 0);
         let set_size = set.len();
-        if idxs.iter().any(|v| *v >= set_size) {
+        if idxs.iter().any(|v| v.index() >= set_size) {
             Err(SubsetError::OutOfBounds)
-        } else { Ok(unsafe{Self::new_unchecked(set, idxs)}) }
+        } else { Ok(unsafe{Self::new_typed_unchecked(set, idxs)}) }
     }
-    /// Constructs a multi-subset from the whole set and indexes of the selected items.
+    /// Constructs a multi-subset from the whole set and indexes of any `Idx` type of the selected items.
     /// No array bounds check.
-    pub unsafe fn new_unchecked(set: &'a [T], idxs: &'a [usize]) -> Self {
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()`.
+    pub unsafe fn new_typed_unchecked(set: &'a [T], idxs: &'a [I]) -> Self {
         Self {
-            set: set,
-            idxs: idxs
+            set,
+            idxs: Cow::Borrowed(idxs)
+        }
+    }
+    /// Constructs a multi-subset that owns indexes of any `Idx` type of the selected items.
+    /// Array bounds is checked.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_typed_vec(set: &'a [T], idxs: Vec<I>) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        let set_size = set.len();
+        if idxs.iter().any(|v| v.index() >= set_size) {
+            Err(SubsetError::OutOfBounds)
+        } else {
+            Ok(Self {
+                set,
+                idxs: Cow::Owned(idxs)
+            })
         }
     }
     /// Returns the original slice.
@@ -114,8 +178,8 @@ impl<'a, T> Subset<'a, T> {
         self.set
     }
     /// Returns indexes of selected items.
-    pub fn idxs(&self) -> &[usize] {
-        self.idxs
+    pub fn idxs(&self) -> &[I] {
+        &self.idxs
     }
     /// Checks that no items are selected twice or more.
     /// if `is_unique() == true` then subset can be converted to unique::Subset.
@@ -133,27 +197,99 @@ impl<'a, T> Subset<'a, T> {
     /// }
     /// ```
     pub fn is_unique(&self) -> bool {
-        is_unique(self.idxs)
+        is_unique(&self.idxs)
     }
     /// Converts to `subset::unique::Subset`.
     /// Uniqueness of indexes is not checked.
-    pub unsafe fn to_unique_unchecked(self) -> unique::Subset<'a, T> {
+    /// 
+    /// # Safety
+    /// No index may occur twice.
+    pub unsafe fn to_unique_unchecked(self) -> unique::Subset<'a, T, I> {
         unique::Subset {
             m: self
         }
     }
     /// Returns an iterator over immutable references to selected items.
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T, I> {
         Iter {
             ptr: self.set.as_ptr(),
             iter: self.idxs.iter()
         }
     }
+    /// Returns every selected index together with the number of times it is selected.
+    /// The result is sorted by index.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::multi::*;
+    /// let set = [1, 2, 3, 4];
+    /// let idxs = [3, 0, 3, 1, 0, 3];
+    /// let subset = Subset::new(&set, &idxs).unwrap();
+    /// assert_eq!(subset.multiplicities(), vec![(0, 2), (1, 1), (3, 3)]);
+    /// ```
+    pub fn multiplicities(&self) -> Vec<(I, usize)> {
+        multiplicities(&self.idxs)
+    }
+    /// Returns indexes that are selected more than once, in ascending order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::multi::*;
+    /// let set = [1, 2, 3, 4];
+    /// let idxs = [3, 0, 3, 1, 0, 3];
+    /// let subset = Subset::new(&set, &idxs).unwrap();
+    /// assert_eq!(subset.duplicates(), vec![0, 3]);
+    /// ```
+    pub fn duplicates(&self) -> Vec<I> {
+        duplicates(&self.idxs)
+    }
+    /// Converts to `subset::unique::Subset` that owns its indexes,
+    /// keeping only the first occurrence of each index.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::multi::*;
+    /// let set = [1, 2, 3, 4];
+    /// let idxs = [3, 0, 3, 1, 0, 3];
+    /// let subset = Subset::new(&set, &idxs).unwrap();
+    /// assert_eq!(subset.dedup_first().idxs(), &[3, 0, 1]);
+    /// ```
+    pub fn dedup_first(&self) -> unique::Subset<'a, T, I> {
+        unique::Subset {
+            m: Self {
+                set: self.set,
+                idxs: Cow::Owned(dedup_first(&self.idxs))
+            }
+        }
+    }
+    /// Converts to `subset::unique::Subset` that owns its indexes,
+    /// keeping only the last occurrence of each index.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::multi::*;
+    /// let set = [1, 2, 3, 4];
+    /// let idxs = [3, 0, 3, 1, 0, 3];
+    /// let subset = Subset::new(&set, &idxs).unwrap();
+    /// assert_eq!(subset.dedup_last().idxs(), &[1, 0, 3]);
+    /// ```
+    pub fn dedup_last(&self) -> unique::Subset<'a, T, I> {
+        unique::Subset {
+            m: Self {
+                set: self.set,
+                idxs: Cow::Owned(dedup_last(&self.idxs))
+            }
+        }
+    }
 }
 
 
-impl<'a, T> From<SubsetMut<'a, T>> for Subset<'a, T> {
-    fn from(s: SubsetMut<'a, T>) -> Self {
+impl<'a, T, I: Idx> From<SubsetMut<'a, T, I>> for Subset<'a, T, I> {
+    fn from(s: SubsetMut<'a, T, I>) -> Self {
         Self {
             set: s.set,
             idxs: s.idxs
@@ -162,39 +298,45 @@ impl<'a, T> From<SubsetMut<'a, T>> for Subset<'a, T> {
 }
 
 
-impl<'a, T> From<unique::Subset<'a, T>> for Subset<'a, T> {
-    fn from(s: unique::Subset<T>) -> Subset<T> {
+impl<'a, T, I: Idx> From<unique::Subset<'a, T, I>> for Subset<'a, T, I> {
+    fn from(s: unique::Subset<'a, T, I>) -> Self {
         s.m
     }
 }
 
 
-impl<'a, T> From<unique::SubsetMut<'a, T>> for Subset<'a, T> {
-    fn from(s: unique::SubsetMut<T>) -> Subset<T> {
+impl<'a, T, I: Idx> From<unique::SubsetMut<'a, T, I>> for Subset<'a, T, I> {
+    fn from(s: unique::SubsetMut<'a, T, I>) -> Self {
         s.m.into()
     }
 }
 
 
-impl<'a, T: 'a> Iterator for Iter<'a, T> {
+impl<'a, T: 'a, I: Idx> Iterator for Iter<'a, T, I> {
     type Item = &'a T;
     fn next(&mut self) -> Option<&'a T> {
         unsafe {
             match self.iter.next() {
                 None => None,
-                Some(idx) => Some(& *self.ptr.offset(*idx as isize))
+                Some(idx) => Some(& *self.ptr.add(idx.index()))
             }
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
 
-impl<'a, T: 'a> DoubleEndedIterator for Iter<'a, T> {
+impl<'a, T: 'a, I: Idx> ExactSizeIterator for Iter<'a, T, I> {}
+
+
+impl<'a, T: 'a, I: Idx> DoubleEndedIterator for Iter<'a, T, I> {
     fn next_back(&mut self) -> Option<&'a T> {
         unsafe {
             match self.iter.next_back() {
                 None => None,
-                Some(idx) => Some(& *self.ptr.offset(*idx as isize))
+                Some(idx) => Some(& *self.ptr.add(idx.index()))
             }
         }
     }
@@ -205,10 +347,9 @@ impl<'a, T: 'a> DoubleEndedIterator for Iter<'a, T> {
 /// Each item of a slice can be selected more than once.
 /// 
 /// The only difference between Subset and SubsetMut is that SubsetMut holds mutable reference to original set.
-#[derive(Debug)]
-pub struct SubsetMut<'a, T> {
+pub struct SubsetMut<'a, T, I: Idx = usize> {
     pub(crate) set: &'a mut [T],
-    pub(crate) idxs: &'a [usize]
+    pub(crate) idxs: Cow<'a, [I]>
 }
 
 impl<'a, T> SubsetMut<'a, T> {
@@ -232,18 +373,76 @@ impl<'a, T> SubsetMut<'a, T> {
     /// # Panics
     /// Panics if `std::mem::size_of::<T>() == 0`
     pub fn new(set: &'a mut [T], idxs: &'a [usize]) -> Result<Self, SubsetError> {
+        Self::new_typed(set, idxs)
+    }
+    /// Constructs a multi-subset from the whole set and indexes of the selected items.
+    /// No array bounds check.
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()`.
+    pub unsafe fn new_unchecked(set: &'a mut [T], idxs: &'a [usize]) -> Self {
+        Self::new_typed_unchecked(set, idxs)
+    }
+    /// Constructs a multi-subset that owns indexes of the selected items.
+    /// Array bounds is checked.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_vec(set: &'a mut [T], idxs: Vec<usize>) -> Result<Self, SubsetError> {
+        Self::from_typed_vec(set, idxs)
+    }
+}
+
+
+impl<'a, T, I: Idx> SubsetMut<'a, T, I> {
+    /// Constructs a multi-subset from the whole set and indexes of any `Idx` type of the selected items.
+    /// Array bounds is checked.
+    /// Note that subsets are not designed for ZSTs.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new_typed(set: &'a mut [T], idxs: &'a [I]) -> Result<Self, SubsetError> {
         assert_ne!(std::mem::size_of::<T>(), 0);
         let set_size = set.len();
-        if idxs.iter().any(|v| *v >= set_size) {
+        if idxs.iter().any(|v| v.index() >= set_size) {
             Err(SubsetError::OutOfBounds)
-        } else { Ok(unsafe{Self::new_unchecked(set, idxs)}) }
+        } else { Ok(unsafe{Self::new_typed_unchecked(set, idxs)}) }
     }
-    /// Constructs a multi-subset from the whole set and indexes of the selected items.
+    /// Constructs a multi-subset from the whole set and indexes of any `Idx` type of the selected items.
     /// No array bounds check.
-    pub unsafe fn new_unchecked(set: &'a mut [T], idxs: &'a [usize]) -> Self {
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()`.
+    pub unsafe fn new_typed_unchecked(set: &'a mut [T], idxs: &'a [I]) -> Self {
         Self {
-            set: set,
-            idxs: idxs
+            set,
+            idxs: Cow::Borrowed(idxs)
+        }
+    }
+    /// Constructs a multi-subset that owns indexes of any `Idx` type of the selected items.
+    /// Array bounds is checked.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= set.len()`
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_typed_vec(set: &'a mut [T], idxs: Vec<I>) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        let set_size = set.len();
+        if idxs.iter().any(|v| v.index() >= set_size) {
+            Err(SubsetError::OutOfBounds)
+        } else {
+            Ok(Self {
+                set,
+                idxs: Cow::Owned(idxs)
+            })
         }
     }
     /// Returns the original slice.
@@ -251,8 +450,8 @@ impl<'a, T> SubsetMut<'a, T> {
         self.set
     }
     /// Returns indexes of selected items.
-    pub fn idxs(&self) -> &[usize] {
-        self.idxs
+    pub fn idxs(&self) -> &[I] {
+        &self.idxs
     }
     /// Checks that no items are selected twice or more.
     /// if `is_unique() == true` then subset can be converted to unique::Subset or unique::SubsetMut.
@@ -270,57 +469,144 @@ impl<'a, T> SubsetMut<'a, T> {
     /// }
     /// ```
     pub fn is_unique(&self) -> bool {
-        is_unique(self.idxs)
+        is_unique(&self.idxs)
     }
     /// Converts to `subset::unique::Subset`.
     /// Uniqueness of indexes is not checked.
-    pub unsafe fn to_unique_unchecked(self) -> unique::Subset<'a, T> {
+    /// 
+    /// # Safety
+    /// No index may occur twice.
+    pub unsafe fn to_unique_unchecked(self) -> unique::Subset<'a, T, I> {
         unique::Subset {
             m: self.into()
         }
     }
     /// Converts to `subset::unique::SubsetMut`.
     /// Uniqueness of indexes is not checked.
-    pub unsafe fn to_unique_mut_unchecked(self) -> unique::SubsetMut<'a, T> {
+    /// 
+    /// # Safety
+    /// No index may occur twice.
+    pub unsafe fn to_unique_mut_unchecked(self) -> unique::SubsetMut<'a, T, I> {
         unique::SubsetMut {
             m: self
         }
     }
     /// Returns an iterator over immutable references to selected items.
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T, I> {
         Iter {
             ptr: self.set.as_ptr(),
             iter: self.idxs.iter()
         }
     }
+    /// Returns every selected index together with the number of times it is selected.
+    /// The result is sorted by index.
+    pub fn multiplicities(&self) -> Vec<(I, usize)> {
+        multiplicities(&self.idxs)
+    }
+    /// Returns indexes that are selected more than once, in ascending order.
+    pub fn duplicates(&self) -> Vec<I> {
+        duplicates(&self.idxs)
+    }
+    /// Converts to `subset::unique::SubsetMut` that owns its indexes,
+    /// keeping only the first occurrence of each index.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::multi::*;
+    /// let mut set = [1, 2, 3, 4];
+    /// let idxs = [3, 0, 3, 1, 0, 3];
+    /// let subset = SubsetMut::new(&mut set, &idxs).unwrap();
+    /// let mut uniq_subset = subset.dedup_first();
+    /// for v in uniq_subset.iter_mut() {
+    ///     *v *= 10;
+    /// }
+    /// assert_eq!(uniq_subset.set(), &[10, 20, 3, 40]);
+    /// ```
+    pub fn dedup_first(self) -> unique::SubsetMut<'a, T, I> {
+        let idxs = dedup_first(&self.idxs);
+        unique::SubsetMut {
+            m: Self {
+                set: self.set,
+                idxs: Cow::Owned(idxs)
+            }
+        }
+    }
+    /// Converts to `subset::unique::SubsetMut` that owns its indexes,
+    /// keeping only the last occurrence of each index.
+    pub fn dedup_last(self) -> unique::SubsetMut<'a, T, I> {
+        let idxs = dedup_last(&self.idxs);
+        unique::SubsetMut {
+            m: Self {
+                set: self.set,
+                idxs: Cow::Owned(idxs)
+            }
+        }
+    }
 }
 
 
-impl<'a, T> From<unique::SubsetMut<'a, T>> for SubsetMut<'a, T> {
-    fn from(s: unique::SubsetMut<T>) -> SubsetMut<T> {
+impl<'a, T, I: Idx> From<unique::SubsetMut<'a, T, I>> for SubsetMut<'a, T, I> {
+    fn from(s: unique::SubsetMut<'a, T, I>) -> Self {
         s.m
     }
 }
 
 
-impl<'a, T> IntoIterator for &'a Subset<'a, T> {
+impl<'a, T, I: Idx> IntoIterator for &'a Subset<'a, T, I> {
     type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
-    fn into_iter(self) -> Iter<'a, T> {
+    type IntoIter = Iter<'a, T, I>;
+    fn into_iter(self) -> Iter<'a, T, I> {
         self.iter()
     }
 }
 
 
-impl<'a, T> IntoIterator for &'a SubsetMut<'a, T> {
+impl<'a, T, I: Idx> IntoIterator for &'a SubsetMut<'a, T, I> {
     type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
-    fn into_iter(self) -> Iter<'a, T> {
+    type IntoIter = Iter<'a, T, I>;
+    fn into_iter(self) -> Iter<'a, T, I> {
         self.iter()
     }
 }
 
 
+fn multiplicities<I: Idx>(idxs: &[I]) -> Vec<(I, usize)> {
+    let mut sorted = idxs.to_vec();
+    sorted.sort_unstable();
+    let mut result: Vec<(I, usize)> = Vec::new();
+    for idx in sorted {
+        match result.last_mut() {
+            Some((last, count)) if *last == idx => *count += 1,
+            _ => result.push((idx, 1))
+        }
+    }
+    result
+}
+
+
+fn duplicates<I: Idx>(idxs: &[I]) -> Vec<I> {
+    multiplicities(idxs).into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+
+fn dedup_first<I: Idx>(idxs: &[I]) -> Vec<I> {
+    let mut seen: HashSet<I> = HashSet::with_capacity(idxs.len());
+    idxs.iter().copied().filter(|idx| seen.insert(*idx)).collect()
+}
+
+
+fn dedup_last<I: Idx>(idxs: &[I]) -> Vec<I> {
+    let mut seen: HashSet<I> = HashSet::with_capacity(idxs.len());
+    let mut result: Vec<I> = idxs.iter().rev().copied().filter(|idx| seen.insert(*idx)).collect();
+    result.reverse();
+    result
+}
+
+
 #[cfg(test)]
 mod tests {
 
@@ -371,4 +657,24 @@ mod tests {
         assert_eq!(*r1, 15);
         assert_eq!(*r2, 15);
     }
+
+    #[test]
+    fn test_dedup() {
+        let mut set = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        let idxs = [5, 2, 5, 2, 9, 5];
+        let subset = Subset::new(&set, &idxs).unwrap();
+        assert_eq!(subset.multiplicities(), vec![(2, 2), (5, 3), (9, 1)]);
+        assert_eq!(subset.duplicates(), vec![2, 5]);
+        let first = subset.dedup_first();
+        assert_eq!(first.idxs(), &[5, 2, 9]);
+        assert_eq!(first.iter().copied().collect::<Vec<_>>(), vec![4, 7, 0]);
+        assert_eq!(subset.dedup_last().idxs(), &[2, 9, 5]);
+        let subset = SubsetMut::new(&mut set, &idxs).unwrap();
+        assert_eq!(subset.duplicates(), vec![2, 5]);
+        let mut last = subset.dedup_last();
+        for v in last.iter_mut() {
+            *v += 100;
+        }
+        assert_eq!(last.set(), vec![9, 8, 107, 6, 5, 104, 3, 2, 1, 100].as_slice());
+    }
 }
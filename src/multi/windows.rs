@@ -0,0 +1,97 @@
+//! Sliding windows over selected items.
+
+use super::{Idx, Subset, SubsetMut};
+use std::borrow::Cow;
+use std::slice;
+
+
+impl<'a, T, I: Idx> Subset<'a, T, I> {
+    /// Returns an iterator over all contiguous windows of `n` selected items, as multi-subsets borrowing indexes of this one.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::multi::*;
+    /// let set = [1, 2, 3, 4];
+    /// let idxs = [3, 0, 0, 2];
+    /// let subset = Subset::new(&set, &idxs).unwrap();
+    /// let sums: Vec<i32> = subset.windows(3).map(|w| w.iter().sum()).collect();
+    /// assert_eq!(sums, vec![6, 5]);
+    /// ```
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> Windows<'_, T, I> {
+        Windows {
+            set: self.set,
+            windows: self.idxs.windows(n)
+        }
+    }
+}
+
+
+impl<'a, T, I: Idx> SubsetMut<'a, T, I> {
+    /// Returns an iterator over all contiguous windows of `n` selected items, as multi-subsets borrowing indexes of this one.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> Windows<'_, T, I> {
+        Windows {
+            set: self.set,
+            windows: self.idxs.windows(n)
+        }
+    }
+}
+
+
+/// Double-ended iterator over contiguous windows of selected items.
+pub struct Windows<'a, T, I = usize> {
+    set: &'a [T],
+    windows: slice::Windows<'a, I>
+}
+
+
+impl<'a, T, I: Idx> Iterator for Windows<'a, T, I> {
+    type Item = Subset<'a, T, I>;
+    fn next(&mut self) -> Option<Subset<'a, T, I>> {
+        self.windows.next().map(|idxs| Subset {
+            set: self.set,
+            idxs: Cow::Borrowed(idxs)
+        })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+
+impl<'a, T, I: Idx> ExactSizeIterator for Windows<'a, T, I> {}
+
+
+impl<'a, T, I: Idx> DoubleEndedIterator for Windows<'a, T, I> {
+    fn next_back(&mut self) -> Option<Subset<'a, T, I>> {
+        self.windows.next_back().map(|idxs| Subset {
+            set: self.set,
+            idxs: Cow::Borrowed(idxs)
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_windows() {
+        let mut set = vec![0, 1, 2];
+        let idxs = [2, 2, 1, 0];
+        let subset = SubsetMut::new(&mut set, &idxs).unwrap();
+        let mut windows = subset.windows(2);
+        assert_eq!(windows.len(), 3);
+        assert!(!windows.next().unwrap().is_unique());
+        assert_eq!(windows.next_back().unwrap().idxs(), &[1, 0]);
+        assert_eq!(subset.windows(5).count(), 0);
+    }
+}
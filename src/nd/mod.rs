@@ -0,0 +1,223 @@
+//! Subsets of subviews along an axis of `ndarray` arrays.
+//! Available with the `ndarray` feature.
+//! 
+//! Like `multi::Subset`, an immutable `Subset` may select a subview more than once,
+//! while `SubsetMut`, like `unique::SubsetMut`, selects every subview no more than once.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use ndarray::{array, Axis};
+//! use subset::nd::*;
+//! 
+//! let mut a = array![[1, 2], [3, 4], [5, 6]];
+//! let idxs = [2, 0, 2];
+//! let subset = Subset::new(a.view(), Axis(0), &idxs).unwrap();
+//! assert_eq!(subset.select_into(), array![[5, 6], [1, 2], [5, 6]]);
+//! assert_eq!(SubsetMut::new(a.view_mut(), Axis(0), &idxs).err(), Some(SubsetError::NotUnique));
+//! 
+//! let idxs = [1];
+//! let mut subset = SubsetMut::new(a.view_mut(), Axis(1), &idxs).unwrap();
+//! for mut col in subset.iter_mut() {
+//!     col.fill(0);
+//! }
+//! assert_eq!(a, array![[1, 0], [3, 0], [5, 0]]);
+//! ```
+
+use ndarray::{Array, ArrayView, ArrayViewMut, Axis, Dimension, RemoveAxis};
+use super::is_unique;
+pub use super::SubsetError;
+
+
+/// Multi-subset of subviews along an axis of an array.
+/// Each subview can be selected more than once.
+pub struct Subset<'a, A, D: Dimension> {
+    view: ArrayView<'a, A, D>,
+    axis: Axis,
+    idxs: &'a [usize]
+}
+
+
+impl<'a, A, D: RemoveAxis> Subset<'a, A, D> {
+    /// Constructs a multi-subset from the array view and indexes of the selected subviews along `axis`.
+    /// Array bounds is checked.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any index is `>= view.len_of(axis)`
+    /// 
+    /// # Panics
+    /// Panics if `axis` is out of bounds.
+    pub fn new(view: ArrayView<'a, A, D>, axis: Axis, idxs: &'a [usize]) -> Result<Self, SubsetError> {
+        let len = view.len_of(axis);
+        if idxs.iter().any(|v| *v >= len) {
+            Err(SubsetError::OutOfBounds)
+        } else { Ok(Self { view, axis, idxs }) }
+    }
+    /// Returns the original array view.
+    pub fn view(&self) -> &ArrayView<'a, A, D> {
+        &self.view
+    }
+    /// Returns the axis of selection.
+    pub fn axis(&self) -> Axis {
+        self.axis
+    }
+    /// Returns indexes of selected subviews.
+    pub fn idxs(&self) -> &[usize] {
+        self.idxs
+    }
+    /// Checks that no subviews are selected twice or more.
+    pub fn is_unique(&self) -> bool {
+        is_unique(self.idxs)
+    }
+    /// Returns the `pos`-th selected subview or `None` if `pos >= idxs().len()`.
+    pub fn get(&self, pos: usize) -> Option<ArrayView<'a, A, D::Smaller>> {
+        let idx = *self.idxs.get(pos)?;
+        Some(self.view.clone().index_axis_move(self.axis, idx))
+    }
+    /// Returns an iterator over selected subviews.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = ArrayView<'a, A, D::Smaller>> + ExactSizeIterator + '_ {
+        self.idxs.iter().map(move |idx| self.view.clone().index_axis_move(self.axis, *idx))
+    }
+    /// Copies selected subviews into an owned array, in selection order.
+    pub fn select_into(&self) -> Array<A, D> where A: Clone {
+        self.view.select(self.axis, self.idxs)
+    }
+    /// Returns an iterator over all contiguous windows of `n` selected subviews, as multi-subsets.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> impl DoubleEndedIterator<Item = Subset<'a, A, D>> + ExactSizeIterator + '_ {
+        self.idxs.windows(n).map(move |idxs| Subset { view: self.view.clone(), axis: self.axis, idxs })
+    }
+}
+
+
+/// Subset of subviews along an axis of an array with mutable access to them.
+/// Each subview can be selected no more than once.
+pub struct SubsetMut<'a, A, D: Dimension> {
+    view: ArrayViewMut<'a, A, D>,
+    axis: Axis,
+    idxs: &'a [usize]
+}
+
+
+impl<'a, A, D: RemoveAxis> SubsetMut<'a, A, D> {
+    /// Constructs a subset from the mutable array view and indexes of the selected subviews along `axis`.
+    /// Both the uniqueness of the selected subviews and the array bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= view.len_of(axis)`
+    /// 
+    /// # Panics
+    /// Panics if `axis` is out of bounds.
+    pub fn new(view: ArrayViewMut<'a, A, D>, axis: Axis, idxs: &'a [usize]) -> Result<Self, SubsetError> {
+        let len = view.len_of(axis);
+        if idxs.iter().any(|v| *v >= len) {
+            Err(SubsetError::OutOfBounds)
+        } else if !is_unique(idxs) {
+            Err(SubsetError::NotUnique)
+        } else { Ok(Self { view, axis, idxs }) }
+    }
+    /// Returns the original array view, reborrowed.
+    pub fn view(&mut self) -> ArrayViewMut<'_, A, D> {
+        self.view.view_mut()
+    }
+    /// Returns the axis of selection.
+    pub fn axis(&self) -> Axis {
+        self.axis
+    }
+    /// Returns indexes of selected subviews.
+    pub fn idxs(&self) -> &[usize] {
+        self.idxs
+    }
+    /// Returns the `pos`-th selected subview or `None` if `pos >= idxs().len()`.
+    pub fn get(&self, pos: usize) -> Option<ArrayView<'_, A, D::Smaller>> {
+        let idx = *self.idxs.get(pos)?;
+        Some(self.view.index_axis(self.axis, idx))
+    }
+    /// Returns the `pos`-th selected subview or `None` if `pos >= idxs().len()`.
+    pub fn get_mut(&mut self, pos: usize) -> Option<ArrayViewMut<'_, A, D::Smaller>> {
+        let idx = *self.idxs.get(pos)?;
+        Some(self.view.index_axis_mut(self.axis, idx))
+    }
+    /// Returns an iterator over selected subviews.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = ArrayView<'_, A, D::Smaller>> + ExactSizeIterator + '_ {
+        let (view, axis) = (self.view.view(), self.axis);
+        self.idxs().iter().map(move |idx| view.clone().index_axis_move(axis, *idx))
+    }
+    /// Returns an iterator over mutable selected subviews.
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = ArrayViewMut<'_, A, D::Smaller>> + ExactSizeIterator + '_ {
+        let (raw, axis) = (self.view.raw_view_mut(), self.axis);
+        // Subviews don't overlap because indexes are unique and checked to be in bounds
+        self.idxs.iter().map(move |idx| unsafe{raw.clone().index_axis_move(axis, *idx).deref_into_view_mut()})
+    }
+    /// Copies selected subviews into an owned array, in selection order.
+    pub fn select_into(&self) -> Array<A, D> where A: Clone {
+        self.view.select(self.axis, self.idxs)
+    }
+    /// Returns an iterator over all contiguous windows of `n` selected subviews, as immutable subsets.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> impl DoubleEndedIterator<Item = Subset<'_, A, D>> + ExactSizeIterator + '_ {
+        let (view, axis) = (self.view.view(), self.axis);
+        self.idxs().windows(n).map(move |idxs| Subset { view: view.clone(), axis, idxs })
+    }
+    /// Assigns subviews of `src` along the axis to selected subviews, in selection order.
+    /// 
+    /// # Panics
+    /// Panics if `src.len_of(axis()) != idxs().len()` or shapes of subviews are different.
+    pub fn scatter(&mut self, src: &ArrayView<'_, A, D>) where A: Clone {
+        assert_eq!(src.len_of(self.axis), self.idxs.len());
+        let axis = self.axis;
+        for (mut dst, values) in self.iter_mut().zip(src.axis_iter(axis)) {
+            dst.assign(&values);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use ndarray::{array, Array3};
+
+    #[test]
+    fn test_set() {
+        let a = Array3::from_shape_fn((2, 3, 4), |(i, j, k)| i * 100 + j * 10 + k);
+        let idxs = [10];
+        assert_eq!(Subset::new(a.view(), Axis(1), &idxs).err(), Some(SubsetError::OutOfBounds));
+        let idxs = [2, 2, 0];
+        let subset = Subset::new(a.view(), Axis(2), &idxs).unwrap();
+        assert!(!subset.is_unique());
+        assert_eq!(subset.get(0).unwrap(), array![[2, 12, 22], [102, 112, 122]]);
+        assert!(subset.get(3).is_none());
+        assert_eq!(subset.iter().next_back().unwrap()[[1, 1]], 110);
+        assert_eq!(subset.select_into().shape(), &[2, 3, 3]);
+        assert_eq!(subset.windows(2).map(|w| w.is_unique()).collect::<Vec<_>>(), vec![false, true]);
+    }
+
+    #[test]
+    fn test_mut() {
+        let mut a = array![[1, 2, 3], [4, 5, 6]];
+        let idxs = [1, 1];
+        assert_eq!(SubsetMut::new(a.view_mut(), Axis(1), &idxs).err(), Some(SubsetError::NotUnique));
+        let idxs = [2, 0];
+        let mut subset = SubsetMut::new(a.view_mut(), Axis(1), &idxs).unwrap();
+        {
+            let mut iter = subset.iter_mut();
+            let mut c2 = iter.next().unwrap();
+            let c0 = iter.next().unwrap();
+            c2 += &c0;
+        }
+        subset.get_mut(1).unwrap().fill(0);
+        subset.view()[[0, 1]] = 50;
+        assert_eq!(subset.select_into(), array![[4, 0], [10, 0]]);
+        assert_eq!(subset.windows(2).next().unwrap().select_into(), array![[4, 0], [10, 0]]);
+        subset.scatter(&array![[7, 8], [9, 10]].view());
+        assert_eq!(subset.iter_mut().rev().map(|c| c.sum()).collect::<Vec<_>>(), vec![18, 16]);
+        assert_eq!(a, array![[8, 50, 7], [10, 5, 9]]);
+    }
+}
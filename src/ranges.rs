@@ -0,0 +1,629 @@
+//! Subsets made of a few contiguous ranges of items, stored as a sorted list of non-overlapping ranges
+//! instead of one index per item.
+//! 
+//! Selected items are always unique and visited in ascending order of their indexes.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use subset::ranges::*;
+//! 
+//! let mut set: Vec<u32> = (0..1000).collect();
+//! assert_eq!(RangeList::new(vec![0..10, 5..20]).err(), Some(SubsetError::NotUnique));
+//! let ranges = RangeList::new(vec![900..1000, 10..20]).unwrap();
+//! let mut subset = RangeSubsetMut::new(&mut set, ranges).unwrap();
+//! assert_eq!(subset.len(), 110);
+//! assert_eq!(subset[10], 900);
+//! for slice in subset.as_mut_slices() {
+//!     slice.fill(0);
+//! }
+//! assert_eq!(set.iter().filter(|v| **v == 0).count(), 111);
+//! 
+//! let a = RangeList::new(vec![0..10, 20..30]).unwrap();
+//! let b = RangeList::new(vec![5..25]).unwrap();
+//! assert_eq!(a.union(&b).ranges(), &[0..30]);
+//! assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+//! assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+//! ```
+
+pub use std::convert::{From,Into,TryFrom,TryInto};
+use std::borrow::Cow;
+use std::iter::{Cloned, Flatten};
+use std::ops::{Index, IndexMut, Range};
+use std::slice;
+use super::{multi, unique};
+pub use super::SubsetError;
+
+
+/// Sorted list of non-overlapping, non-adjacent and non-empty ranges of indexes.
+#[derive(Debug,Clone,Default,PartialEq,Eq,Hash)]
+pub struct RangeList {
+    ranges: Vec<Range<usize>>,
+    offsets: Vec<usize>    // offsets[i] is the number of indexes in ranges[..i]
+}
+
+
+impl RangeList {
+    /// Constructs a range list from ranges in any order.
+    /// Empty ranges are dropped and adjacent ones are merged.
+    /// 
+    /// # Errors
+    /// NotUnique, if any two ranges overlap.
+    pub fn new(mut ranges: Vec<Range<usize>>) -> Result<Self, SubsetError> {
+        ranges.retain(|r| !r.is_empty());
+        ranges.sort_unstable_by_key(|r| r.start);
+        if ranges.windows(2).any(|w| w[1].start < w[0].end) {
+            Err(SubsetError::NotUnique)
+        } else { Ok(Self::merge_sorted(ranges)) }
+    }
+    /// Constructs a range list from indexes in any order, grouping consecutive indexes into ranges.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `usize::MAX`, since no range can contain it.
+    pub fn from_idxs(idxs: &[usize]) -> Result<Self, SubsetError> {
+        let mut sorted = idxs.to_vec();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|w| w[0] == w[1]) {
+            return Err(SubsetError::NotUnique);
+        }
+        let ranges = sorted.into_iter()
+            .map(|idx| idx.checked_add(1).map(|end| idx..end).ok_or(SubsetError::OutOfBounds))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::merge_sorted(ranges))
+    }
+    /// Merges overlapping or adjacent non-empty ranges sorted by start.
+    fn merge_sorted(ranges: Vec<Range<usize>>) -> Self {
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r)
+            }
+        }
+        let mut offsets = Vec::with_capacity(merged.len());
+        let mut len = 0;
+        for r in &merged {
+            offsets.push(len);
+            len += r.len();
+        }
+        Self { ranges: merged, offsets }
+    }
+    /// Returns the sorted ranges.
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+    /// Returns the number of indexes in all ranges.
+    pub fn len(&self) -> usize {
+        match (self.ranges.last(), self.offsets.last()) {
+            (Some(r), Some(offset)) => offset + r.len(),
+            _ => 0
+        }
+    }
+    /// Returns `true` if there are no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    /// Returns the end of the last range, i.e. the minimal length of slices the list fits in.
+    pub fn end(&self) -> usize {
+        self.ranges.last().map_or(0, |r| r.end)
+    }
+    /// Checks whether `idx` is in any range, in O(log r).
+    pub fn contains(&self, idx: usize) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= idx);
+        self.ranges.get(i).is_some_and(|r| r.start <= idx)
+    }
+    /// Returns the `pos`-th index or `None` if `pos >= len()`, in O(log r).
+    pub fn get(&self, pos: usize) -> Option<usize> {
+        let i = self.offsets.partition_point(|offset| *offset <= pos).checked_sub(1)?;
+        let idx = self.ranges[i].start + (pos - self.offsets[i]);
+        if idx < self.ranges[i].end {
+            Some(idx)
+        } else { None }
+    }
+    /// Returns an iterator over all indexes in ascending order.
+    pub fn idxs(&self) -> Idxs<'_> {
+        self.ranges.iter().cloned().flatten()
+    }
+    /// Returns indexes that are in `self` or in `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges: Vec<_> = self.ranges.iter().chain(&other.ranges).cloned().collect();
+        ranges.sort_unstable_by_key(|r| r.start);
+        Self::merge_sorted(ranges)
+    }
+    /// Returns indexes that are both in `self` and in `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end { i += 1 } else { j += 1 }
+        }
+        Self::merge_sorted(ranges)
+    }
+    /// Returns indexes that are in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for a in &self.ranges {
+            let mut start = a.start;
+            // Skip ranges of `other` that end before the current piece
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < a.end {
+                let b = &other.ranges[k];
+                if start < b.start {
+                    ranges.push(start..b.start);
+                }
+                start = start.max(b.end);
+                k += 1;
+            }
+            if start < a.end {
+                ranges.push(start..a.end);
+            }
+        }
+        Self::merge_sorted(ranges)
+    }
+}
+
+
+/// Double-ended iterator over indexes of a range list.
+pub type Idxs<'s> = Flatten<Cloned<slice::Iter<'s, Range<usize>>>>;
+
+
+/// Double-ended iterator over immutable references to selected items of set, that walks the ranges.
+pub struct Iter<'s, T> {
+    set: &'s [T],
+    ranges: slice::Iter<'s, Range<usize>>,
+    front: slice::Iter<'s, T>,
+    back: slice::Iter<'s, T>,
+    len: usize
+}
+
+
+impl<'s, T> Iter<'s, T> {
+    fn new(set: &'s [T], ranges: &'s RangeList) -> Self {
+        Self {
+            set,
+            ranges: ranges.ranges.iter(),
+            front: Default::default(),
+            back: Default::default(),
+            len: ranges.len()
+        }
+    }
+}
+
+
+impl<'s, T> Iterator for Iter<'s, T> {
+    type Item = &'s T;
+    fn next(&mut self) -> Option<&'s T> {
+        loop {
+            if let Some(v) = self.front.next() {
+                self.len -= 1;
+                return Some(v);
+            }
+            match self.ranges.next() {
+                Some(r) => self.front = self.set[r.clone()].iter(),
+                None => {
+                    let v = self.back.next()?;
+                    self.len -= 1;
+                    return Some(v);
+                }
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+
+impl<'s, T> ExactSizeIterator for Iter<'s, T> {}
+
+
+impl<'s, T> DoubleEndedIterator for Iter<'s, T> {
+    fn next_back(&mut self) -> Option<&'s T> {
+        loop {
+            if let Some(v) = self.back.next_back() {
+                self.len -= 1;
+                return Some(v);
+            }
+            match self.ranges.next_back() {
+                Some(r) => self.back = self.set[r.clone()].iter(),
+                None => {
+                    let v = self.front.next_back()?;
+                    self.len -= 1;
+                    return Some(v);
+                }
+            }
+        }
+    }
+}
+
+
+/// Double-ended iterator over mutable references to selected items of set, that walks the ranges.
+pub struct IterMut<'s, T> {
+    rest: &'s mut [T],    // Items between the front and the back ranges
+    start: usize,         // Index of the first item of `rest`
+    ranges: slice::Iter<'s, Range<usize>>,
+    front: slice::IterMut<'s, T>,
+    back: slice::IterMut<'s, T>,
+    len: usize
+}
+
+
+impl<'s, T> IterMut<'s, T> {
+    fn new(set: &'s mut [T], ranges: &'s RangeList) -> Self {
+        Self {
+            rest: set,
+            start: 0,
+            ranges: ranges.ranges.iter(),
+            front: Default::default(),
+            back: Default::default(),
+            len: ranges.len()
+        }
+    }
+}
+
+
+impl<'s, T> Iterator for IterMut<'s, T> {
+    type Item = &'s mut T;
+    fn next(&mut self) -> Option<&'s mut T> {
+        loop {
+            if let Some(v) = self.front.next() {
+                self.len -= 1;
+                return Some(v);
+            }
+            match self.ranges.next() {
+                Some(r) => {
+                    let rest = std::mem::take(&mut self.rest);
+                    let (slice, tail) = rest[r.start - self.start..].split_at_mut(r.len());
+                    self.front = slice.iter_mut();
+                    self.rest = tail;
+                    self.start = r.end;
+                }
+                None => {
+                    let v = self.back.next()?;
+                    self.len -= 1;
+                    return Some(v);
+                }
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+
+impl<'s, T> ExactSizeIterator for IterMut<'s, T> {}
+
+
+impl<'s, T> DoubleEndedIterator for IterMut<'s, T> {
+    fn next_back(&mut self) -> Option<&'s mut T> {
+        loop {
+            if let Some(v) = self.back.next_back() {
+                self.len -= 1;
+                return Some(v);
+            }
+            match self.ranges.next_back() {
+                Some(r) => {
+                    let rest = std::mem::take(&mut self.rest);
+                    let (head, slice) = rest[..r.end - self.start].split_at_mut(r.start - self.start);
+                    self.back = slice.iter_mut();
+                    self.rest = head;
+                }
+                None => {
+                    let v = self.front.next_back()?;
+                    self.len -= 1;
+                    return Some(v);
+                }
+            }
+        }
+    }
+}
+
+
+/// Subset of slice's items made of contiguous ranges that is able to iterate forward and backward over immutable references to selected items.
+/// Each item of a slice can be selected no more than once.
+pub struct RangeSubset<'a, T> {
+    pub(crate) set: &'a [T],
+    pub(crate) ranges: RangeList
+}
+
+
+impl<'a, T> RangeSubset<'a, T> {
+    /// Constructs a subset from the whole set and ranges of the selected items.
+    /// Array bounds is checked in O(1).
+    /// 
+    /// # Errors
+    /// OutOfBounds, if `ranges.end() > set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new(set: &'a [T], ranges: RangeList) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        if ranges.end() > set.len() {
+            Err(SubsetError::OutOfBounds)
+        } else { Ok(Self { set, ranges }) }
+    }
+    /// Returns the original slice.
+    pub fn set(&self) -> &[T] {
+        self.set
+    }
+    /// Returns ranges of selected items.
+    pub fn ranges(&self) -> &RangeList {
+        &self.ranges
+    }
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`, in O(log r).
+    pub fn get(&self, pos: usize) -> Option<&'a T> {
+        self.ranges.get(pos).map(|idx| &self.set[idx])
+    }
+    /// Returns one slice per range of selected items.
+    pub fn as_slices(&self) -> Vec<&'a [T]> {
+        self.ranges.ranges.iter().map(|r| &self.set[r.clone()]).collect()
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.set, &self.ranges)
+    }
+}
+
+
+impl<'a, T> From<RangeSubsetMut<'a, T>> for RangeSubset<'a, T> {
+    fn from(s: RangeSubsetMut<'a, T>) -> Self {
+        Self {
+            set: s.set,
+            ranges: s.ranges
+        }
+    }
+}
+
+
+impl<'a, T> From<RangeSubset<'a, T>> for unique::Subset<'a, T> {
+    fn from(s: RangeSubset<'a, T>) -> Self {
+        let m = multi::Subset {
+            idxs: Cow::Owned(s.ranges.idxs().collect()),
+            set: s.set
+        };
+        unsafe{m.to_unique_unchecked()}
+    }
+}
+
+
+impl<'a, T> unique::Subset<'a, T> {
+    /// Converts to a range subset, grouping consecutive indexes into ranges.
+    /// Selected items are reordered by ascending index.
+    pub fn sorted_into_ranges(self) -> RangeSubset<'a, T> {
+        // Indexes of a unique subset are unique and below `set.len()`, so never `usize::MAX`
+        RangeSubset {
+            ranges: RangeList::from_idxs(&self.m.idxs).expect("indexes of a unique subset are unique and in bounds"),
+            set: self.m.set
+        }
+    }
+}
+
+
+impl<'a, T> Index<usize> for RangeSubset<'a, T> {
+    type Output = T;
+    fn index(&self, pos: usize) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a RangeSubset<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+
+/// Subset of slice's items made of contiguous ranges that is able to iterate forward and backward over mutable or immutable references to selected items.
+/// Each item of a slice can be selected no more than once.
+pub struct RangeSubsetMut<'a, T> {
+    pub(crate) set: &'a mut [T],
+    pub(crate) ranges: RangeList
+}
+
+
+impl<'a, T> RangeSubsetMut<'a, T> {
+    /// Constructs a subset from the whole set and ranges of the selected items.
+    /// Array bounds is checked in O(1).
+    /// 
+    /// # Errors
+    /// OutOfBounds, if `ranges.end() > set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new(set: &'a mut [T], ranges: RangeList) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        if ranges.end() > set.len() {
+            Err(SubsetError::OutOfBounds)
+        } else { Ok(Self { set, ranges }) }
+    }
+    /// Returns the original slice.
+    pub fn set(&mut self) -> &mut [T] {
+        self.set
+    }
+    /// Returns ranges of selected items.
+    pub fn ranges(&self) -> &RangeList {
+        &self.ranges
+    }
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`, in O(log r).
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        self.ranges.get(pos).map(|idx| &self.set[idx])
+    }
+    /// Returns a mutable reference to the `pos`-th selected item or `None` if `pos >= len()`, in O(log r).
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        self.ranges.get(pos).map(move |idx| &mut self.set[idx])
+    }
+    /// Returns one slice per range of selected items.
+    pub fn as_slices(&self) -> Vec<&[T]> {
+        self.ranges.ranges.iter().map(|r| &self.set[r.clone()]).collect()
+    }
+    /// Returns one mutable slice per range of selected items.
+    pub fn as_mut_slices(&mut self) -> Vec<&mut [T]> {
+        let mut slices = Vec::with_capacity(self.ranges.ranges.len());
+        let mut rest: &mut [T] = self.set;
+        let mut consumed = 0;
+        for r in &self.ranges.ranges {
+            let (_, tail) = std::mem::take(&mut rest).split_at_mut(r.start - consumed);
+            let (slice, tail) = tail.split_at_mut(r.len());
+            slices.push(slice);
+            rest = tail;
+            consumed = r.end;
+        }
+        slices
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.set, &self.ranges)
+    }
+    /// Returns an iterator over mutable references to selected items.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(self.set, &self.ranges)
+    }
+}
+
+
+impl<'a, T> From<RangeSubsetMut<'a, T>> for unique::SubsetMut<'a, T> {
+    fn from(s: RangeSubsetMut<'a, T>) -> Self {
+        let m = multi::SubsetMut {
+            idxs: Cow::Owned(s.ranges.idxs().collect()),
+            set: s.set
+        };
+        unsafe{m.to_unique_mut_unchecked()}
+    }
+}
+
+
+impl<'a, T> unique::SubsetMut<'a, T> {
+    /// Converts to a range subset, grouping consecutive indexes into ranges.
+    /// Selected items are reordered by ascending index.
+    pub fn sorted_into_ranges(self) -> RangeSubsetMut<'a, T> {
+        // Indexes of a unique subset are unique and below `set.len()`, so never `usize::MAX`
+        RangeSubsetMut {
+            ranges: RangeList::from_idxs(&self.m.idxs).expect("indexes of a unique subset are unique and in bounds"),
+            set: self.m.set
+        }
+    }
+}
+
+
+impl<'a, T> Index<usize> for RangeSubsetMut<'a, T> {
+    type Output = T;
+    fn index(&self, pos: usize) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IndexMut<usize> for RangeSubsetMut<'a, T> {
+    fn index_mut(&mut self, pos: usize) -> &mut T {
+        self.get_mut(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a RangeSubsetMut<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a mut RangeSubsetMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_range_list() {
+        assert_eq!(RangeList::from_idxs(&[3, 1, 3]).err(), Some(SubsetError::NotUnique));
+        let list = RangeList::from_idxs(&[7, 1, 2, 3, 9, 8]).unwrap();
+        assert_eq!(list.ranges(), &[1..4, 7..10]);
+        let list = RangeList::new(vec![7..10, 0..0, 4..7, 1..3]).unwrap();
+        assert_eq!(list.ranges(), &[1..3, 4..10]);
+        assert_eq!(list.len(), 8);
+        assert_eq!(list.end(), 10);
+        assert_eq!((0..9).map(|pos| list.get(pos)).collect::<Vec<_>>(),
+            vec![Some(1), Some(2), Some(4), Some(5), Some(6), Some(7), Some(8), Some(9), None]);
+        assert!(list.contains(4) && !list.contains(3) && !list.contains(10));
+        assert_eq!(list.idxs().rev().take(2).collect::<Vec<_>>(), vec![9, 8]);
+        let other = RangeList::new(vec![0..2, 5..6, 8..12]).unwrap();
+        assert_eq!(list.union(&other).ranges(), &[0..3, 4..12]);
+        assert_eq!(list.intersection(&other).ranges(), &[1..2, 5..6, 8..10]);
+        assert_eq!(list.difference(&other).ranges(), &[2..3, 4..5, 6..8]);
+        assert_eq!(other.difference(&list).ranges(), &[0..1, 10..12]);
+        assert!(RangeList::default().get(0).is_none());
+        assert_eq!(RangeList::from_idxs(&[usize::MAX]).err(), Some(SubsetError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_subset() {
+        let mut set = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let ranges = RangeList::new(vec![0..2, 8..11]).unwrap();
+        assert_eq!(RangeSubset::new(&set, ranges).err(), Some(SubsetError::OutOfBounds));
+        let ranges = RangeList::new(vec![7..9, 1..3]).unwrap();
+        let mut subset = RangeSubsetMut::new(&mut set, ranges).unwrap();
+        assert_eq!(subset.as_slices(), vec![&[1, 2][..], &[7, 8][..]]);
+        subset[3] = 80;
+        for v in subset.iter_mut().rev().take(2) {
+            *v += 100;
+        }
+        let subset: unique::SubsetMut<_> = subset.into();
+        assert_eq!(subset.idxs(), &[1, 2, 7, 8]);
+        assert_eq!(set, vec![0, 1, 2, 3, 4, 5, 6, 107, 180, 9]);
+        let idxs = [9, 0, 1];
+        let subset = unique::Subset::new(&set, &idxs).unwrap().sorted_into_ranges();
+        assert_eq!(subset.ranges().ranges(), &[0..2, 9..10]);
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![0, 1, 9]);
+        let mut iter = subset.iter();
+        assert_eq!((iter.next_back(), iter.next(), iter.len()), (Some(&9), Some(&0), 1));
+        assert_eq!((iter.next_back(), iter.next()), (Some(&1), None));
+        let ranges = RangeList::new(vec![0..1, 3..5, 8..10]).unwrap();
+        let mut subset = RangeSubsetMut::new(&mut set, ranges).unwrap();
+        let mut iter = subset.iter_mut();
+        *iter.next_back().unwrap() = -9;
+        *iter.next().unwrap() = 10;
+        *iter.next_back().unwrap() = -8;
+        assert_eq!(iter.map(|v| { *v = -*v; *v }).collect::<Vec<_>>(), vec![-3, -4]);
+        assert_eq!(set, vec![10, 1, 2, -3, -4, 5, 6, 107, -8, -9]);
+    }
+}
@@ -0,0 +1,228 @@
+//! Serde support: subsets are serialized as sequences of selected values,
+//! index lists are serialized as is and validated again on deserialization.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use subset::unique::{SubsetMut, ValidatedIdxs};
+//! use subset::PatchSeed;
+//! use serde::de::DeserializeSeed;
+//! 
+//! let mut state = [0, 10, 20, 30, 40];
+//! let dirty = ValidatedIdxs::new(vec![3, 1], state.len()).unwrap();
+//! let subset = SubsetMut::from_validated(&mut state, &dirty).unwrap();
+//! let update = serde_json::to_string(&(&dirty, &subset)).unwrap();
+//! assert_eq!(update, r#"[{"idxs":[3,1],"max_len":5},[30,10]]"#);
+//! 
+//! let mut replica = [0, 1, 2, 3, 4];
+//! let (dirty, values): (ValidatedIdxs, serde_json::Value) = serde_json::from_str(&update).unwrap();
+//! let mut subset = SubsetMut::from_validated(&mut replica, &dirty).unwrap();
+//! PatchSeed::new(&mut subset).deserialize(values).unwrap();
+//! assert_eq!(replica, [0, 10, 2, 30, 4]);
+//! ```
+
+use super::{compressed, multi, ranges, strided, unique, Idx, SubsetError, SubsetView};
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde::ser::{Serializer, SerializeStruct};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+
+
+macro_rules! impl_serialize {
+    ([$($gen:tt)*] $ty:ty) => {
+        impl<'a, T: Serialize, $($gen)*> Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(SubsetView::iter(self))
+            }
+        }
+    };
+}
+
+impl_serialize!([I: Idx] multi::Subset<'a, T, I>);
+impl_serialize!([I: Idx] multi::SubsetMut<'a, T, I>);
+impl_serialize!([I: Idx] unique::Subset<'a, T, I>);
+impl_serialize!([I: Idx] unique::SubsetMut<'a, T, I>);
+impl_serialize!([const N: usize] unique::FixedSubsetMut<'a, T, N>);
+impl_serialize!([] strided::StridedSubset<'a, T>);
+impl_serialize!([] strided::StridedSubsetMut<'a, T>);
+impl_serialize!([] ranges::RangeSubset<'a, T>);
+impl_serialize!([] ranges::RangeSubsetMut<'a, T>);
+impl_serialize!([] compressed::CompressedSubset<'a, T>);
+impl_serialize!([] compressed::CompressedSubsetMut<'a, T>);
+#[cfg(feature = "roaring")]
+impl_serialize!([] compressed::RoaringSubset<'a, T>);
+#[cfg(feature = "roaring")]
+impl_serialize!([] compressed::RoaringSubsetMut<'a, T>);
+
+
+fn invalid<E: de::Error>(e: SubsetError) -> E {
+    E::custom(format_args!("invalid index list: {:?}", e))
+}
+
+
+impl Serialize for unique::ValidatedIdxs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("ValidatedIdxs", 2)?;
+        s.serialize_field("idxs", self.idxs())?;
+        s.serialize_field("max_len", &self.max_len())?;
+        s.end()
+    }
+}
+
+
+#[derive(Deserialize)]
+#[serde(rename = "ValidatedIdxs")]
+struct ValidatedIdxsRepr {
+    idxs: Vec<usize>,
+    max_len: usize
+}
+
+
+impl<'de> Deserialize<'de> for unique::ValidatedIdxs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = ValidatedIdxsRepr::deserialize(deserializer)?;
+        unique::ValidatedIdxs::new(repr.idxs, repr.max_len).map_err(invalid)
+    }
+}
+
+
+impl Serialize for unique::Permutation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.idxs().serialize(serializer)
+    }
+}
+
+
+impl<'de> Deserialize<'de> for unique::Permutation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        unique::Permutation::new(Vec::deserialize(deserializer)?).map_err(invalid)
+    }
+}
+
+
+impl Serialize for ranges::RangeList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.ranges().serialize(serializer)
+    }
+}
+
+
+impl<'de> Deserialize<'de> for ranges::RangeList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ranges::RangeList::new(Vec::<Range<usize>>::deserialize(deserializer)?).map_err(invalid)
+    }
+}
+
+
+impl Serialize for compressed::CompressedIdxs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+
+impl<'de> Deserialize<'de> for compressed::CompressedIdxs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<usize>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+
+/// Deserializes a sequence of values directly into selected items of a subset, in selection order.
+/// 
+/// The sequence must have exactly as many values as there are selected items.
+/// Values are buffered and written only after the whole sequence is read,
+/// so on error the subset is left untouched.
+pub struct PatchSeed<'s, 'a, T, I: Idx = usize> {
+    subset: &'s mut unique::SubsetMut<'a, T, I>
+}
+
+
+impl<'s, 'a, T, I: Idx> PatchSeed<'s, 'a, T, I> {
+    /// Constructs a seed overwriting selected items of `subset`.
+    pub fn new(subset: &'s mut unique::SubsetMut<'a, T, I>) -> Self {
+        Self { subset }
+    }
+}
+
+
+impl<'de, 's, 'a, T: Deserialize<'de>, I: Idx> DeserializeSeed<'de> for PatchSeed<'s, 'a, T, I> {
+    type Value = ();
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+
+impl<'de, 's, 'a, T: Deserialize<'de>, I: Idx> Visitor<'de> for PatchSeed<'s, 'a, T, I> {
+    type Value = ();
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of {} values", self.subset.idxs().len())
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let len = self.subset.idxs().len();
+        let expected = format!("{} values", len);
+        if let Some(hint) = seq.size_hint().filter(|hint| *hint != len) {
+            return Err(de::Error::invalid_length(hint, &expected.as_str()));
+        }
+        let mut values = Vec::with_capacity(len);
+        while values.len() < len {
+            match seq.next_element()? {
+                Some(value) => values.push(value),
+                None => return Err(de::Error::invalid_length(values.len(), &expected.as_str()))
+            }
+        }
+        let mut extra = 0;
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            extra += 1;
+        }
+        if extra > 0 {
+            return Err(de::Error::invalid_length(len + extra, &expected.as_str()));
+        }
+        self.subset.iter_mut().zip(values).for_each(|(v, value)| *v = value);
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let mut set = vec![1.5, 2.5, 3.5, 4.5];
+        let subset = multi::Subset::new(&set, &[3, 0, 3]).unwrap();
+        assert_eq!(serde_json::to_string(&subset).unwrap(), "[4.5,1.5,4.5]");
+        let subset = strided::StridedSubset::new(&set, 1, 2, 2).unwrap();
+        assert_eq!(serde_json::to_string(&subset).unwrap(), "[2.5,4.5]");
+        assert!(serde_json::from_str::<unique::Permutation>("[1,1]").is_err());
+        assert!(serde_json::from_str::<unique::ValidatedIdxs>(r#"{"idxs":[4],"max_len":4}"#).is_err());
+        let ranges: ranges::RangeList = serde_json::from_str(r#"[{"start":2,"end":4}]"#).unwrap();
+        assert_eq!(serde_json::to_string(&ranges).unwrap(), r#"[{"start":2,"end":4}]"#);
+        assert!(serde_json::from_str::<ranges::RangeList>(r#"[{"start":0,"end":2},{"start":1,"end":3}]"#).is_err());
+        let idxs: compressed::CompressedIdxs = serde_json::from_str("[5,0,5]").unwrap();
+        assert_eq!(serde_json::to_string(&idxs).unwrap(), "[5,0,5]");
+        #[cfg(feature = "roaring")]
+        {
+            let bitmap: roaring::RoaringBitmap = [3, 1].iter().copied().collect();
+            let subset = compressed::RoaringSubset::new(&set, &bitmap).unwrap();
+            assert_eq!(serde_json::to_string(&subset).unwrap(), "[2.5,4.5]");
+            let subset = compressed::RoaringSubsetMut::new(&mut set, &bitmap).unwrap();
+            assert_eq!(serde_json::to_string(&subset).unwrap(), "[2.5,4.5]");
+        }
+        let idxs = [2, 0];
+        let mut subset = unique::SubsetMut::new(&mut set, &idxs).unwrap();
+        let mut de = serde_json::Deserializer::from_str("[0.0]");
+        assert!(PatchSeed::new(&mut subset).deserialize(&mut de).is_err());
+        let mut de = serde_json::Deserializer::from_str("[1.0,2.0,3.0]");
+        assert!(PatchSeed::new(&mut subset).deserialize(&mut de).is_err());
+        assert!(PatchSeed::new(&mut subset).deserialize(serde_json::json!([5.0, "x"])).is_err());
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![3.5, 1.5]);
+        let mut de = serde_json::Deserializer::from_str("[-1.0,-2.0]");
+        PatchSeed::new(&mut subset).deserialize(&mut de).unwrap();
+        assert_eq!(set, vec![-2.0, 2.5, -1.0, 4.5]);
+    }
+}
@@ -0,0 +1,526 @@
+//! Subsets selecting an arithmetic progression of items `start, start + step, ..., start + step * (count - 1)`
+//! without storing their indexes.
+//! 
+//! Selected items are unique whenever `step != 0`, so `StridedSubsetMut` is able to iterate over mutable references,
+//! while `StridedSubset` may also select the same item `count` times with `step == 0`.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use subset::strided::*;
+//! 
+//! let mut set = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+//! assert_eq!(StridedSubset::new(&set, 5, 3, 3).err(), Some(SubsetError::OutOfBounds));
+//! assert_eq!(StridedSubsetMut::new(&mut set, 5, 0, 3).err(), Some(SubsetError::NotUnique));
+//! 
+//! // Every 3rd item starting at 1
+//! let mut subset = StridedSubsetMut::new(&mut set, 1, 3, 3).unwrap();
+//! assert_eq!(subset.iter().collect::<Vec<_>>(), vec![&1, &4, &7]);
+//! for v in subset.iter_mut() {
+//!     *v *= 10;
+//! }
+//! subset[2] += 1;
+//! assert_eq!(subset.into_multi().idxs(), &[1, 4, 7]);
+//! assert_eq!(set, [0, 10, 2, 3, 40, 5, 6, 71, 8, 9]);
+//! ```
+
+pub use std::convert::{From,Into,TryFrom,TryInto};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut, Range};
+use super::{multi, unique};
+pub use super::SubsetError;
+
+
+/// Checks that the last selected index is `< len` without overflowing.
+fn check_bounds(len: usize, start: usize, step: usize, count: usize) -> Result<(), SubsetError> {
+    if count == 0 {
+        return Ok(());
+    }
+    match step.checked_mul(count - 1).and_then(|offset| offset.checked_add(start)) {
+        Some(last) if last < len => Ok(()),
+        _ => Err(SubsetError::OutOfBounds)
+    }
+}
+
+
+/// Double-ended iterator over indexes of an arithmetic progression.
+#[derive(Debug,Clone)]
+pub struct StridedIdxs {
+    start: usize,
+    step: usize,
+    range: Range<usize>    // Positions in the progression left to yield
+}
+
+
+impl Iterator for StridedIdxs {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        self.range.next().map(|pos| self.start + pos * self.step)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+
+impl ExactSizeIterator for StridedIdxs {}
+
+
+impl DoubleEndedIterator for StridedIdxs {
+    fn next_back(&mut self) -> Option<usize> {
+        self.range.next_back().map(|pos| self.start + pos * self.step)
+    }
+}
+
+
+/// Strided subset of slice's items that is able to iterate forward and backward over immutable references to selected items.
+/// Each item of a slice can be selected more than once, but only if `step == 0`.
+pub struct StridedSubset<'a, T> {
+    pub(crate) set: &'a [T],
+    pub(crate) start: usize,
+    pub(crate) step: usize,
+    pub(crate) count: usize
+}
+
+
+impl<'a, T> StridedSubset<'a, T> {
+    /// Constructs a subset selecting `count` items of `set` starting at `start` with the distance `step` between them.
+    /// Array bounds is checked in O(1).
+    /// 
+    /// # Errors
+    /// OutOfBounds, if `start + step * (count - 1) >= set.len()` for `count > 0`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new(set: &'a [T], start: usize, step: usize, count: usize) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        check_bounds(set.len(), start, step, count)?;
+        Ok(Self { set, start, step, count })
+    }
+    /// Returns the original slice.
+    pub fn set(&self) -> &[T] {
+        self.set
+    }
+    /// Returns the index of the first selected item.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+    /// Returns the distance between indexes of neighbouring selected items.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// Checks that no items are selected twice or more.
+    pub fn is_unique(&self) -> bool {
+        self.step != 0 || self.count <= 1
+    }
+    /// Returns an iterator over indexes of selected items.
+    pub fn idxs(&self) -> StridedIdxs {
+        StridedIdxs {
+            start: self.start,
+            step: self.step,
+            range: 0..self.count
+        }
+    }
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get(&self, pos: usize) -> Option<&'a T> {
+        if pos < self.count {
+            Some(&self.set[self.start + pos * self.step])
+        } else { None }
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter {
+            set: self.set,
+            idxs: self.idxs()
+        }
+    }
+    /// Returns an iterator over all contiguous windows of `n` selected items, as strided subsets.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::strided::*;
+    /// let set = [0, 1, 2, 3, 4, 5, 6];
+    /// let subset = StridedSubset::new(&set, 0, 2, 4).unwrap();
+    /// let sums: Vec<i32> = subset.windows(2).map(|w| w.iter().sum()).collect();
+    /// assert_eq!(sums, vec![2, 6, 10]);
+    /// ```
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> Windows<'a, T> {
+        windows(self.set, self.start, self.step, self.count, n)
+    }
+    /// Converts to a multi-subset owning materialized indexes of the selected items.
+    pub fn into_multi(self) -> multi::Subset<'a, T> {
+        multi::Subset {
+            idxs: Cow::Owned(self.idxs().collect()),
+            set: self.set
+        }
+    }
+}
+
+
+impl<'a, T> From<StridedSubsetMut<'a, T>> for StridedSubset<'a, T> {
+    fn from(s: StridedSubsetMut<'a, T>) -> Self {
+        Self {
+            set: s.set,
+            start: s.start,
+            step: s.step,
+            count: s.count
+        }
+    }
+}
+
+
+impl<'a, T> From<StridedSubset<'a, T>> for multi::Subset<'a, T> {
+    fn from(s: StridedSubset<'a, T>) -> Self {
+        s.into_multi()
+    }
+}
+
+
+impl<'a, T> TryFrom<StridedSubset<'a, T>> for unique::Subset<'a, T> {
+    type Error = SubsetError;
+    fn try_from(s: StridedSubset<'a, T>) -> Result<Self, SubsetError> {
+        if s.is_unique() {
+            Ok(unsafe{s.into_multi().to_unique_unchecked()})
+        } else {
+            Err(SubsetError::NotUnique)
+        }
+    }
+}
+
+
+impl<'a, T> Index<usize> for StridedSubset<'a, T> {
+    type Output = T;
+    fn index(&self, pos: usize) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IntoIterator for &StridedSubset<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+
+/// Double-ended iterator over immutable references to selected items of set.
+#[derive(Debug,Clone)]
+pub struct Iter<'a, T> {
+    set: &'a [T],
+    idxs: StridedIdxs
+}
+
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.idxs.next().map(|idx| &self.set[idx])
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idxs.size_hint()
+    }
+}
+
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.idxs.next_back().map(|idx| &self.set[idx])
+    }
+}
+
+
+fn windows<T>(set: &[T], start: usize, step: usize, count: usize, n: usize) -> Windows<'_, T> {
+    assert_ne!(n, 0, "window size must be non-zero");
+    Windows {
+        set,
+        n,
+        starts: StridedIdxs {
+            start,
+            step,
+            range: 0..count.saturating_sub(n - 1)
+        }
+    }
+}
+
+
+/// Double-ended iterator over contiguous windows of selected items.
+#[derive(Debug,Clone)]
+pub struct Windows<'a, T> {
+    set: &'a [T],
+    n: usize,
+    starts: StridedIdxs    // Indexes of the first items of windows left to yield
+}
+
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = StridedSubset<'a, T>;
+    fn next(&mut self) -> Option<StridedSubset<'a, T>> {
+        let start = self.starts.next()?;
+        Some(StridedSubset {
+            set: self.set,
+            start,
+            step: self.starts.step,
+            count: self.n
+        })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.starts.size_hint()
+    }
+}
+
+
+impl<'a, T> ExactSizeIterator for Windows<'a, T> {}
+
+
+impl<'a, T> DoubleEndedIterator for Windows<'a, T> {
+    fn next_back(&mut self) -> Option<StridedSubset<'a, T>> {
+        let start = self.starts.next_back()?;
+        Some(StridedSubset {
+            set: self.set,
+            start,
+            step: self.starts.step,
+            count: self.n
+        })
+    }
+}
+
+
+/// Strided subset of slice's items that is able to iterate forward and backward over mutable or immutable references to selected items.
+/// Each item of a slice can be selected no more than once.
+pub struct StridedSubsetMut<'a, T> {
+    pub(crate) set: &'a mut [T],
+    pub(crate) start: usize,
+    pub(crate) step: usize,
+    pub(crate) count: usize
+}
+
+
+impl<'a, T> StridedSubsetMut<'a, T> {
+    /// Constructs a subset selecting `count` items of `set` starting at `start` with the distance `step` between them.
+    /// Both the uniqueness of the selected items and the array bounds is checked in O(1).
+    /// 
+    /// # Errors
+    /// NotUnique, if `step == 0` and `count > 1`.
+    /// OutOfBounds, if `start + step * (count - 1) >= set.len()` for `count > 0`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new(set: &'a mut [T], start: usize, step: usize, count: usize) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        check_bounds(set.len(), start, step, count)?;
+        if step == 0 && count > 1 {
+            Err(SubsetError::NotUnique)
+        } else { Ok(Self { set, start, step, count }) }
+    }
+    /// Returns the original slice.
+    pub fn set(&mut self) -> &mut [T] {
+        self.set
+    }
+    /// Returns the index of the first selected item.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+    /// Returns the distance between indexes of neighbouring selected items.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// Returns an iterator over indexes of selected items.
+    pub fn idxs(&self) -> StridedIdxs {
+        StridedIdxs {
+            start: self.start,
+            step: self.step,
+            range: 0..self.count
+        }
+    }
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        if pos < self.count {
+            Some(&self.set[self.start + pos * self.step])
+        } else { None }
+    }
+    /// Returns a mutable reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        if pos < self.count {
+            Some(&mut self.set[self.start + pos * self.step])
+        } else { None }
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            set: self.set,
+            idxs: self.idxs()
+        }
+    }
+    /// Returns an iterator over all contiguous windows of `n` selected items, as strided subsets.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> Windows<'_, T> {
+        windows(self.set, self.start, self.step, self.count, n)
+    }
+    /// Returns an iterator over mutable references to selected items.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            ptr: self.set.as_mut_ptr(),
+            idxs: self.idxs(),
+            _marker: PhantomData
+        }
+    }
+    /// Converts to a multi-subset owning materialized indexes of the selected items.
+    pub fn into_multi(self) -> multi::SubsetMut<'a, T> {
+        multi::SubsetMut {
+            idxs: Cow::Owned(self.idxs().collect()),
+            set: self.set
+        }
+    }
+}
+
+
+impl<'a, T> From<StridedSubsetMut<'a, T>> for multi::SubsetMut<'a, T> {
+    fn from(s: StridedSubsetMut<'a, T>) -> Self {
+        s.into_multi()
+    }
+}
+
+
+impl<'a, T> From<StridedSubsetMut<'a, T>> for unique::SubsetMut<'a, T> {
+    fn from(s: StridedSubsetMut<'a, T>) -> Self {
+        unsafe{s.into_multi().to_unique_mut_unchecked()}
+    }
+}
+
+
+impl<'a, T> Index<usize> for StridedSubsetMut<'a, T> {
+    type Output = T;
+    fn index(&self, pos: usize) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IndexMut<usize> for StridedSubsetMut<'a, T> {
+    fn index_mut(&mut self, pos: usize) -> &mut T {
+        self.get_mut(pos).expect("position out of bounds")
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a StridedSubsetMut<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+
+impl<'a, T> IntoIterator for &'a mut StridedSubsetMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+
+/// Double-ended iterator over mutable references to selected items of set.
+pub struct IterMut<'a, T> {
+    ptr: *mut T,    // Points to the set
+    idxs: StridedIdxs,
+    _marker: PhantomData<&'a mut T>
+}
+
+
+impl<'a, T: 'a> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<&'a mut T> {
+        let idx = self.idxs.next()?;
+        Some(unsafe{&mut *self.ptr.add(idx)})
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idxs.size_hint()
+    }
+}
+
+
+impl<'a, T: 'a> ExactSizeIterator for IterMut<'a, T> {}
+
+
+impl<'a, T: 'a> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        let idx = self.idxs.next_back()?;
+        Some(unsafe{&mut *self.ptr.add(idx)})
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_set() {
+        let set = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        assert_eq!(StridedSubset::new(&set, 10, 1, 1).err(), Some(SubsetError::OutOfBounds));
+        assert_eq!(StridedSubset::new(&set, 1, usize::MAX, 3).err(), Some(SubsetError::OutOfBounds));
+        assert!(StridedSubset::new(&set, 100, 1, 0).unwrap().is_empty());
+        let subset = StridedSubset::new(&set, 9, 0, 3).unwrap();
+        assert!(!subset.is_unique());
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![9, 9, 9]);
+        let result: Result<unique::Subset<_>, _> = subset.try_into();
+        assert_eq!(result.err(), Some(SubsetError::NotUnique));
+        let subset = StridedSubset::new(&set, 2, 3, 3).unwrap();
+        assert_eq!(subset[1], 5);
+        assert_eq!(subset.get(3), None);
+        assert_eq!(subset.idxs().rev().collect::<Vec<_>>(), vec![8, 5, 2]);
+        assert_eq!(subset.windows(2).next_back().unwrap().idxs().collect::<Vec<_>>(), vec![5, 8]);
+        assert_eq!(subset.windows(4).len(), 0);
+        let subset: unique::Subset<_> = subset.try_into().unwrap();
+        assert_eq!(subset.idxs(), &[2, 5, 8]);
+    }
+
+    #[test]
+    fn test_mut() {
+        let mut set = vec![0, 1, 2, 3, 4, 5];
+        assert_eq!(StridedSubsetMut::new(&mut set, 0, 2, 4).err(), Some(SubsetError::OutOfBounds));
+        assert_eq!(StridedSubsetMut::new(&mut set, 0, 0, 2).err(), Some(SubsetError::NotUnique));
+        assert_eq!(StridedSubsetMut::new(&mut set, 3, 0, 1).unwrap().len(), 1);
+        let mut subset = StridedSubsetMut::new(&mut set, 0, 2, 3).unwrap();
+        let mut iter = subset.iter_mut();
+        let last = iter.next_back().unwrap();
+        let first = iter.next().unwrap();
+        std::mem::swap(first, last);
+        assert_eq!(iter.len(), 1);
+        subset[1] = 20;
+        let subset: unique::SubsetMut<_> = subset.into();
+        assert_eq!(subset.idxs(), &[0, 2, 4]);
+        assert_eq!(set, vec![4, 1, 20, 3, 0, 5]);
+    }
+}
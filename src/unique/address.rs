@@ -0,0 +1,158 @@
+//! Iterating over selected items in the order of the set.
+
+use super::{Idx, Subset, SubsetMut};
+use std::marker::PhantomData;
+use std::vec;
+
+
+/// Returns pairs of positions and indexes of selected items in ascending order of indexes.
+fn address_order<I: Idx>(idxs: &[I]) -> Vec<(usize, usize)> {
+    let mut order: Vec<(usize, usize)> = idxs.iter().map(|idx| idx.index()).enumerate().collect();
+    order.sort_unstable_by_key(|&(_, idx)| idx);
+    order
+}
+
+
+impl<'a, T, I: Idx> Subset<'a, T, I> {
+    /// Returns an iterator over selected items in ascending order of their indexes in the set,
+    /// that yields positions of items in the selection together with immutable references to them.
+    /// The order is computed once, by sorting a copy of indexes.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let set = [0, 10, 20, 30];
+    /// let idxs = [3, 0, 2];
+    /// let subset = Subset::new(&set, &idxs).unwrap();
+    /// assert_eq!(subset.iter_by_address().collect::<Vec<_>>(), vec![(1, &0), (2, &20), (0, &30)]);
+    /// ```
+    pub fn iter_by_address(&self) -> ByAddressIter<'a, T> {
+        ByAddressIter {
+            set: self.m.set,
+            order: address_order(self.idxs()).into_iter()
+        }
+    }
+}
+
+
+impl<'a, T, I: Idx> SubsetMut<'a, T, I> {
+    /// Returns an iterator over selected items in ascending order of their indexes in the set,
+    /// that yields positions of items in the selection together with immutable references to them.
+    /// The order is computed once, by sorting a copy of indexes.
+    pub fn iter_by_address(&self) -> ByAddressIter<'_, T> {
+        ByAddressIter {
+            set: self.m.set,
+            order: address_order(self.idxs()).into_iter()
+        }
+    }
+    /// Returns an iterator over selected items in ascending order of their indexes in the set,
+    /// that yields positions of items in the selection together with mutable references to them.
+    /// The order is computed once, by sorting a copy of indexes.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let mut set = [5, 6, 7, 8];
+    /// let idxs = [2, 0, 3];
+    /// let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+    /// let mut old = vec![0; idxs.len()];
+    /// for (pos, v) in subset.iter_mut_by_address() {
+    ///     old[pos] = *v;
+    ///     *v = 0;
+    /// }
+    /// assert_eq!(old, vec![7, 5, 8]);
+    /// assert_eq!(set, [0, 6, 0, 0]);
+    /// ```
+    pub fn iter_mut_by_address(&mut self) -> ByAddressIterMut<'_, T> {
+        ByAddressIterMut {
+            ptr: self.m.set.as_mut_ptr(),
+            order: address_order(&self.m.idxs).into_iter(),
+            _marker: PhantomData
+        }
+    }
+}
+
+
+/// Double-ended iterator over positions in the selection and immutable references to selected items of set,
+/// in ascending order of indexes.
+pub struct ByAddressIter<'a, T> {
+    set: &'a [T],
+    order: vec::IntoIter<(usize, usize)>
+}
+
+
+impl<'a, T> Iterator for ByAddressIter<'a, T> {
+    type Item = (usize, &'a T);
+    fn next(&mut self) -> Option<(usize, &'a T)> {
+        self.order.next().map(|(pos, idx)| (pos, &self.set[idx]))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+
+impl<'a, T> ExactSizeIterator for ByAddressIter<'a, T> {}
+
+
+impl<'a, T> DoubleEndedIterator for ByAddressIter<'a, T> {
+    fn next_back(&mut self) -> Option<(usize, &'a T)> {
+        self.order.next_back().map(|(pos, idx)| (pos, &self.set[idx]))
+    }
+}
+
+
+/// Double-ended iterator over positions in the selection and mutable references to selected items of set,
+/// in ascending order of indexes.
+pub struct ByAddressIterMut<'a, T> {
+    ptr: *mut T,    // Points to the set
+    order: vec::IntoIter<(usize, usize)>,
+    _marker: PhantomData<&'a mut T>
+}
+
+
+impl<'a, T: 'a> Iterator for ByAddressIterMut<'a, T> {
+    type Item = (usize, &'a mut T);
+    fn next(&mut self) -> Option<(usize, &'a mut T)> {
+        let (pos, idx) = self.order.next()?;
+        Some((pos, unsafe{&mut *self.ptr.add(idx)}))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+
+impl<'a, T: 'a> ExactSizeIterator for ByAddressIterMut<'a, T> {}
+
+
+impl<'a, T: 'a> DoubleEndedIterator for ByAddressIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<(usize, &'a mut T)> {
+        let (pos, idx) = self.order.next_back()?;
+        Some((pos, unsafe{&mut *self.ptr.add(idx)}))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_by_address() {
+        let mut set = vec![0, 1, 2, 3, 4, 5];
+        let idxs: [u16; 4] = [4, 1, 5, 0];
+        let mut subset = SubsetMut::new_typed(&mut set, &idxs).unwrap();
+        assert_eq!(subset.iter_by_address().map(|(pos, _)| pos).collect::<Vec<_>>(), vec![3, 1, 0, 2]);
+        let mut iter = subset.iter_mut_by_address();
+        assert_eq!(iter.len(), 4);
+        let (_, last) = iter.next_back().unwrap();
+        let (_, first) = iter.next().unwrap();
+        std::mem::swap(first, last);
+        let subset: Subset<_, _> = subset.into();
+        assert_eq!(subset.iter_by_address().rev().map(|(_, v)| *v).collect::<Vec<_>>(), vec![0, 4, 1, 5]);
+    }
+}
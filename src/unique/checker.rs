@@ -0,0 +1,158 @@
+//! Uniqueness check with reusable scratch memory.
+
+use super::{is_unique_pairwise, multi, SubsetError, Subset, SubsetMut};
+
+
+const PAIRWISE_MAX_LEN: usize = 16;
+const BITS: usize = u64::BITS as usize;
+
+
+/// Checks uniqueness of indexes, reusing its scratch memory between calls.
+/// 
+/// The algorithm is chosen by the number of indexes relative to the set length:
+/// pairwise comparison for tiny lists, a bitmap for dense lists and sorting for sparse ones.
+/// It checks `usize` indexes only.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut checker = UniquenessChecker::new();
+/// let mut set = [9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+/// for idxs in [[2, 4, 7], [1, 4, 1]].iter() {
+///     match SubsetMut::new_with_checker(&mut set, idxs, &mut checker) {
+///         Ok(mut subset) => subset.iter_mut().for_each(|v| *v = 0),
+///         Err(e) => assert_eq!(e, SubsetError::NotUnique)
+///     }
+/// }
+/// assert_eq!(set, [9, 8, 0, 6, 0, 4, 3, 0, 1, 0]);
+/// ```
+#[derive(Debug,Default,Clone)]
+pub struct UniquenessChecker {
+    bitmap: Vec<u64>,   // All bits are cleared between calls
+    sorted: Vec<usize>
+}
+
+
+impl UniquenessChecker {
+    /// Constructs a checker without allocating scratch memory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Checks that no index occurs twice.
+    /// 
+    /// # Panics
+    /// May panic if any index is `>= set_len`.
+    pub fn is_unique(&mut self, idxs: &[usize], set_len: usize) -> bool {
+        if idxs.len() <= PAIRWISE_MAX_LEN {
+            is_unique_pairwise(idxs)
+        } else if set_len / BITS <= idxs.len() {
+            self.is_unique_bitmap(idxs, set_len)
+        } else {
+            self.is_unique_sorted(idxs)
+        }
+    }
+    fn is_unique_bitmap(&mut self, idxs: &[usize], set_len: usize) -> bool {
+        let words = set_len.div_ceil(BITS);
+        if self.bitmap.len() < words {
+            self.bitmap.resize(words, 0);
+        }
+        let mut checked = idxs.len();
+        for (i, idx) in idxs.iter().enumerate() {
+            let (word, bit) = (idx / BITS, 1 << (idx % BITS));
+            if self.bitmap[word] & bit != 0 {
+                checked = i;
+                break;
+            }
+            self.bitmap[word] |= bit;
+        }
+        // Clearing only touched words keeps the cost proportional to idxs.len()
+        for idx in &idxs[..checked] {
+            self.bitmap[idx / BITS] = 0;
+        }
+        checked == idxs.len()
+    }
+    fn is_unique_sorted(&mut self, idxs: &[usize]) -> bool {
+        self.sorted.clear();
+        self.sorted.extend_from_slice(idxs);
+        self.sorted.sort_unstable();
+        self.sorted.windows(2).all(|w| w[0] != w[1])
+    }
+}
+
+
+impl<'a, T> Subset<'a, T> {
+    /// Constructs a subset from the whole set and indexes of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked,
+    /// the former using scratch memory of `checker`.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new_with_checker(set: &'a [T], idxs: &'a [usize], checker: &mut UniquenessChecker) -> Result<Self, SubsetError> {
+        let m = multi::Subset::new(set, idxs)?;
+        if checker.is_unique(idxs, set.len()) {
+            Ok(unsafe{m.to_unique_unchecked()})
+        } else {
+            Err(SubsetError::NotUnique)
+        }
+    }
+}
+
+
+impl<'a, T> SubsetMut<'a, T> {
+    /// Constructs a subset from the whole set and indexes of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked,
+    /// the former using scratch memory of `checker`.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new_with_checker(set: &'a mut [T], idxs: &'a [usize], checker: &mut UniquenessChecker) -> Result<Self, SubsetError> {
+        let set_len = set.len();
+        let m = multi::SubsetMut::new(set, idxs)?;
+        if checker.is_unique(idxs, set_len) {
+            Ok(unsafe{m.to_unique_mut_unchecked()})
+        } else {
+            Err(SubsetError::NotUnique)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_checker() {
+        let mut checker = UniquenessChecker::new();
+        // Pairwise
+        assert!(checker.is_unique(&[3, 1, 2], 4));
+        assert!(!checker.is_unique(&[3, 1, 3], 4));
+        // Bitmap
+        let mut idxs: Vec<usize> = (0..100).rev().collect();
+        assert!(checker.is_unique(&idxs, 100));
+        idxs.push(70);
+        assert!(!checker.is_unique(&idxs, 100));
+        assert!(checker.bitmap.iter().all(|w| *w == 0));
+        idxs.pop();
+        assert!(checker.is_unique(&idxs, 100));
+        // Sorting
+        let mut idxs: Vec<usize> = (0..20).map(|i| i * 1000).collect();
+        assert!(checker.is_unique(&idxs, 20_000));
+        idxs.push(5000);
+        assert!(!checker.is_unique(&idxs, 20_000));
+        let set = vec![0u8; 20_000];
+        assert_eq!(Subset::new_with_checker(&set, &idxs, &mut checker).err(), Some(SubsetError::NotUnique));
+        idxs.push(20_000);
+        assert_eq!(Subset::new_with_checker(&set, &idxs, &mut checker).err(), Some(SubsetError::OutOfBounds));
+    }
+}
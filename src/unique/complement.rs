@@ -0,0 +1,252 @@
+//! Iterating over items of a set that are not selected by a subset.
+
+use super::{multi, Idx, IterMut, Subset, SubsetMut};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+
+/// Double-ended iterator over indexes of unselected items, driven by a bitmap of selected items.
+struct ComplementIdxs {
+    selected: Vec<u64>,
+    front: usize,
+    back: usize,
+    remaining: usize
+}
+
+
+impl ComplementIdxs {
+    /// Every index must be `< len` and no index may occur twice.
+    fn new<I: Idx>(len: usize, idxs: &[I]) -> Self {
+        let mut selected = vec![0u64; len.div_ceil(64)];
+        for idx in idxs.iter().map(|idx| idx.index()) {
+            selected[idx / 64] |= 1 << (idx % 64);
+        }
+        Self {
+            selected,
+            front: 0,
+            back: len,
+            remaining: len - idxs.len()
+        }
+    }
+    fn is_selected(&self, idx: usize) -> bool {
+        self.selected[idx / 64] & (1 << (idx % 64)) != 0
+    }
+}
+
+
+impl Iterator for ComplementIdxs {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        while self.front < self.back {
+            let idx = self.front;
+            self.front += 1;
+            if !self.is_selected(idx) {
+                self.remaining -= 1;
+                return Some(idx);
+            }
+        }
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+
+impl DoubleEndedIterator for ComplementIdxs {
+    fn next_back(&mut self) -> Option<usize> {
+        while self.front < self.back {
+            self.back -= 1;
+            if !self.is_selected(self.back) {
+                self.remaining -= 1;
+                return Some(self.back);
+            }
+        }
+        None
+    }
+}
+
+
+impl<'a, T, I: Idx> Subset<'a, T, I> {
+    /// Returns an iterator over immutable references to items that are not selected, in the order of the set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let set = [0, 1, 2, 3, 4];
+    /// let idxs = [3, 0];
+    /// let subset = Subset::new(&set, &idxs).unwrap();
+    /// assert_eq!(subset.complement_iter().collect::<Vec<_>>(), vec![&1, &2, &4]);
+    /// ```
+    pub fn complement_iter(&self) -> ComplementIter<'a, T> {
+        ComplementIter {
+            set: self.m.set,
+            idxs: ComplementIdxs::new(self.m.set.len(), self.idxs())
+        }
+    }
+    /// Returns a subset that owns indexes of items that are not selected, in ascending order.
+    pub fn complement(&self) -> Subset<'a, T> {
+        let idxs = ComplementIdxs::new(self.m.set.len(), self.idxs()).collect();
+        Subset {
+            m: multi::Subset {
+                set: self.m.set,
+                idxs: Cow::Owned(idxs)
+            }
+        }
+    }
+}
+
+
+impl<'a, T, I: Idx> SubsetMut<'a, T, I> {
+    /// Returns an iterator over immutable references to items that are not selected, in the order of the set.
+    pub fn complement_iter(&self) -> ComplementIter<'_, T> {
+        ComplementIter {
+            set: self.m.set,
+            idxs: ComplementIdxs::new(self.m.set.len(), self.idxs())
+        }
+    }
+    /// Returns an iterator over mutable references to items that are not selected, in the order of the set.
+    pub fn complement_iter_mut(&mut self) -> ComplementIterMut<'_, T> {
+        ComplementIterMut {
+            ptr: self.m.set.as_mut_ptr(),
+            idxs: ComplementIdxs::new(self.m.set.len(), &self.m.idxs),
+            _marker: PhantomData
+        }
+    }
+    /// Returns a subset that owns indexes of items that are not selected, in ascending order.
+    pub fn complement(&self) -> Subset<'_, T> {
+        let idxs = ComplementIdxs::new(self.m.set.len(), self.idxs()).collect();
+        Subset {
+            m: multi::Subset {
+                set: self.m.set,
+                idxs: Cow::Owned(idxs)
+            }
+        }
+    }
+    /// Returns a mutable subset that owns indexes of items that are not selected, in ascending order.
+    pub fn complement_mut(&mut self) -> SubsetMut<'_, T> {
+        let idxs = ComplementIdxs::new(self.m.set.len(), &self.m.idxs).collect();
+        SubsetMut {
+            m: multi::SubsetMut {
+                set: self.m.set,
+                idxs: Cow::Owned(idxs)
+            }
+        }
+    }
+    /// Returns iterators over mutable references to selected items and to items that are not selected.
+    /// Both can be used at the same time, since no item is yielded by both.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let mut set = [5, 1, 4, 2, 3];
+    /// let idxs = [0, 2];
+    /// let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+    /// let (selected, rest) = subset.split_complement_mut();
+    /// for (a, b) in selected.zip(rest) {
+    ///     std::mem::swap(a, b);
+    /// }
+    /// assert_eq!(set, [1, 5, 2, 4, 3]);
+    /// ```
+    pub fn split_complement_mut(&mut self) -> (IterMut<'_, T, I>, ComplementIterMut<'_, T>) {
+        let ptr = self.m.set.as_mut_ptr();
+        let complement = ComplementIterMut {
+            ptr,
+            idxs: ComplementIdxs::new(self.m.set.len(), &self.m.idxs),
+            _marker: PhantomData
+        };
+        let selected = IterMut {
+            ptr,
+            iter: self.m.idxs.iter()
+        };
+        (selected, complement)
+    }
+}
+
+
+/// Double-ended iterator over immutable references to items of set that are not selected.
+pub struct ComplementIter<'a, T> {
+    set: &'a [T],
+    idxs: ComplementIdxs
+}
+
+
+impl<'a, T> Iterator for ComplementIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.idxs.next().map(|idx| &self.set[idx])
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idxs.size_hint()
+    }
+}
+
+
+impl<'a, T> ExactSizeIterator for ComplementIter<'a, T> {}
+
+
+impl<'a, T> DoubleEndedIterator for ComplementIter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.idxs.next_back().map(|idx| &self.set[idx])
+    }
+}
+
+
+/// Double-ended iterator over mutable references to items of set that are not selected.
+pub struct ComplementIterMut<'a, T> {
+    ptr: *mut T,    // Points to the set
+    idxs: ComplementIdxs,
+    _marker: PhantomData<&'a mut T>
+}
+
+
+impl<'a, T: 'a> Iterator for ComplementIterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<&'a mut T> {
+        let idx = self.idxs.next()?;
+        Some(unsafe{&mut *self.ptr.add(idx)})
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idxs.size_hint()
+    }
+}
+
+
+impl<'a, T: 'a> ExactSizeIterator for ComplementIterMut<'a, T> {}
+
+
+impl<'a, T: 'a> DoubleEndedIterator for ComplementIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        let idx = self.idxs.next_back()?;
+        Some(unsafe{&mut *self.ptr.add(idx)})
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_complement() {
+        let mut set: Vec<usize> = (0..130).collect();
+        let idxs: Vec<u8> = vec![129, 0, 64, 63];
+        let mut subset = SubsetMut::new_typed(&mut set, &idxs).unwrap();
+        assert_eq!(subset.complement_iter().len(), 126);
+        assert_eq!(subset.complement_iter().next_back(), Some(&128));
+        for v in subset.complement_iter_mut() {
+            *v = 0;
+        }
+        assert_eq!(subset.complement().idxs()[..3], [1, 2, 3]);
+        let mut complement = subset.complement_mut();
+        complement.iter_mut().for_each(|v| *v += 1);
+        let (selected, rest) = subset.split_complement_mut();
+        assert_eq!(selected.map(|v| *v).collect::<Vec<_>>(), vec![129, 0, 64, 63]);
+        assert_eq!(rest.rev().take(2).map(|v| *v).collect::<Vec<_>>(), vec![1, 1]);
+        let subset = Subset::new(&set, &[]).unwrap();
+        assert_eq!(subset.complement().idxs().len(), set.len());
+    }
+}
@@ -0,0 +1,163 @@
+//! Cursors over selected items that are able to mutate neighbouring items at the same time.
+
+use super::{Idx, SubsetMut};
+use std::marker::PhantomData;
+
+
+impl<'a, T, I: Idx> SubsetMut<'a, T, I> {
+    /// Returns a cursor pointing to the first selected item, or to the "ghost" position if no items are selected.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, I> {
+        CursorMut {
+            ptr: self.m.set.as_mut_ptr(),
+            idxs: &self.m.idxs,
+            pos: 0,
+            _marker: PhantomData
+        }
+    }
+}
+
+
+/// Cursor over selected items of a subset that is able to move forward and backward
+/// and to mutate the current item together with its neighbours in the selection.
+/// 
+/// Besides positions of selected items, a cursor can point to the "ghost" position between the last and the first ones,
+/// where there is no current item.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut path = [10, 0, 0, 0, 50];
+/// let idxs = [4, 2, 0];
+/// let mut subset = SubsetMut::new(&mut path, &idxs).unwrap();
+/// let mut cursor = subset.cursor_mut();
+/// while let Some((prev, current, next)) = cursor.neighbours_mut() {
+///     if let (Some(prev), Some(next)) = (prev, next) {
+///         *current = (*prev + *next) / 2;
+///     }
+///     cursor.move_next();
+/// }
+/// assert_eq!(path, [10, 0, 30, 0, 50]);
+/// ```
+pub struct CursorMut<'a, T, I = usize> {
+    ptr: *mut T,    // Points to the set
+    idxs: &'a [I],
+    pos: usize,    // `idxs.len()` for the ghost position
+    _marker: PhantomData<&'a mut T>
+}
+
+
+impl<'a, T, I: Idx> CursorMut<'a, T, I> {
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.idxs.len()
+    }
+    /// Returns `true` if no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.idxs.is_empty()
+    }
+    /// Returns the position of the current item in the selection, or `None` at the ghost position.
+    pub fn position(&self) -> Option<usize> {
+        if self.pos < self.idxs.len() {
+            Some(self.pos)
+        } else {
+            None
+        }
+    }
+    /// Moves the cursor to the `pos`-th selected item, or to the ghost position if `pos >= len()`.
+    pub fn set_index(&mut self, pos: usize) {
+        self.pos = pos.min(self.idxs.len());
+    }
+    /// Moves the cursor to the next selected item.
+    /// Moves from the last item to the ghost position and from the ghost position to the first item.
+    pub fn move_next(&mut self) {
+        self.pos = self.next_pos();
+    }
+    /// Moves the cursor to the previous selected item.
+    /// Moves from the first item to the ghost position and from the ghost position to the last item.
+    pub fn move_prev(&mut self) {
+        self.pos = self.prev_pos();
+    }
+    /// Returns a mutable reference to the current item, or `None` at the ghost position.
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.item(self.pos).map(|v| unsafe{&mut *v})
+    }
+    /// Returns a mutable reference to the next item, or `None` if the next position is the ghost one.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        self.item(self.next_pos()).map(|v| unsafe{&mut *v})
+    }
+    /// Returns a mutable reference to the previous item, or `None` if the previous position is the ghost one.
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        self.item(self.prev_pos()).map(|v| unsafe{&mut *v})
+    }
+    /// Returns mutable references to the current and the next items,
+    /// or `None` if any of them is at the ghost position.
+    pub fn current_and_next_mut(&mut self) -> Option<(&mut T, &mut T)> {
+        let current = self.item(self.pos)?;
+        let next = self.item(self.next_pos())?;
+        // Positions differ, so indexes differ
+        Some(unsafe{(&mut *current, &mut *next)})
+    }
+    /// Returns mutable references to the previous, the current and the next items,
+    /// or `None` at the ghost position.
+    pub fn neighbours_mut(&mut self) -> Option<(Option<&mut T>, &mut T, Option<&mut T>)> {
+        let current = self.item(self.pos)?;
+        let prev = self.item(self.prev_pos());
+        let next = self.item(self.next_pos());
+        // Positions differ, so indexes differ
+        unsafe {
+            Some((prev.map(|v| &mut *v), &mut *current, next.map(|v| &mut *v)))
+        }
+    }
+    fn next_pos(&self) -> usize {
+        if self.pos < self.idxs.len() {
+            self.pos + 1
+        } else {
+            0
+        }
+    }
+    fn prev_pos(&self) -> usize {
+        if self.pos == 0 {
+            self.idxs.len()
+        } else {
+            self.pos - 1
+        }
+    }
+    /// Returns a pointer to the `pos`-th selected item, or `None` at the ghost position.
+    fn item(&self, pos: usize) -> Option<*mut T> {
+        let idx = self.idxs.get(pos)?;
+        Some(unsafe{self.ptr.add(idx.index())})
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_cursor() {
+        let mut set = vec![0, 1, 2, 3];
+        let idxs = [3, 1];
+        let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+        let mut cursor = subset.cursor_mut();
+        assert_eq!(cursor.position(), Some(0));
+        assert_eq!(cursor.peek_prev(), None);
+        let (current, next) = cursor.current_and_next_mut().unwrap();
+        std::mem::swap(current, next);
+        cursor.move_next();
+        assert_eq!(cursor.current_and_next_mut(), None);
+        cursor.move_next();
+        assert_eq!(cursor.position(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        cursor.move_prev();
+        *cursor.current().unwrap() = 10;
+        cursor.set_index(7);
+        assert_eq!(cursor.position(), None);
+        cursor.set_index(0);
+        assert_eq!(cursor.peek_next(), Some(&mut 10));
+        assert_eq!(set, vec![0, 10, 2, 1]);
+    }
+}
@@ -0,0 +1,185 @@
+//! Fixed-size unique subsets whose indexes are stored inline.
+
+use super::{is_unique_pairwise, multi, IterMut, SubsetError, SubsetMut};
+use std::borrow::Cow;
+
+
+/// Returns mutable references to `N` distinct items of a slice.
+/// Uniqueness is checked by pairwise comparison, so nothing is allocated.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut set = [9, 8, 7, 6, 5];
+/// let [a, b] = get_many_mut(&mut set, [1, 3]).unwrap();
+/// std::mem::swap(a, b);
+/// assert_eq!(set, [9, 6, 7, 8, 5]);
+/// assert_eq!(get_many_mut(&mut set, [1, 1]).err(), Some(SubsetError::NotUnique));
+/// assert_eq!(get_many_mut(&mut set, [1, 5]).err(), Some(SubsetError::OutOfBounds));
+/// ```
+/// 
+/// # Errors
+/// NotUnique, if there are duplicate indexes.
+/// OutOfBounds, if any index is `>= set.len()`.
+/// 
+/// # Panics
+/// Panics if `std::mem::size_of::<T>() == 0`
+pub fn get_many_mut<T, const N: usize>(set: &mut [T], idxs: [usize; N]) -> Result<[&mut T; N], SubsetError> {
+    FixedSubsetMut::new(set, idxs).map(FixedSubsetMut::into_array)
+}
+
+
+/// Subset of exactly `N` slice's items that is able to iterate forward and backward over mutable or immutable references to selected items.
+/// Each item of a slice can be selected no more than once.
+/// 
+/// Unlike `SubsetMut`, indexes are stored inline and uniqueness is checked without allocation.
+/// Indexes are always `usize`.
+pub struct FixedSubsetMut<'a, T, const N: usize> {
+    pub(crate) set: &'a mut [T],
+    pub(crate) idxs: [usize; N]
+}
+
+
+impl<'a, T, const N: usize> FixedSubsetMut<'a, T, N> {
+    /// Constructs a subset from the whole set and indexes of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked.
+    /// Note that subsets are not designed for ZSTs.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let mut set = [1.0, 1.1, 1.2];
+    /// let mut subset = FixedSubsetMut::new(&mut set, [2, 0]).unwrap();
+    /// *subset.get_mut(0) = 3.1415;
+    /// assert_eq!(subset.set(), &[1.0, 1.1, 3.1415]);
+    /// ```
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new(set: &'a mut [T], idxs: [usize; N]) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        let set_size = set.len();
+        if idxs.iter().any(|v| *v >= set_size) {
+            Err(SubsetError::OutOfBounds)
+        } else if !is_unique_pairwise(&idxs) {
+            Err(SubsetError::NotUnique)
+        } else { Ok(unsafe{Self::new_unchecked(set, idxs)}) }
+    }
+    /// Constructs a subset from the whole set and indexes of the selected items.
+    /// Neither the uniqueness of the selected items, nor the array bounds is checked.
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()` and no index may occur twice.
+    pub unsafe fn new_unchecked(set: &'a mut [T], idxs: [usize; N]) -> Self {
+        Self {
+            set,
+            idxs
+        }
+    }
+    /// Returns the original slice.
+    pub fn set(&mut self) -> &mut [T] {
+        self.set
+    }
+    /// Returns indexes of selected items.
+    pub fn idxs(&self) -> &[usize; N] {
+        &self.idxs
+    }
+    /// Returns a reference to the `pos`-th selected item.
+    /// 
+    /// # Panics
+    /// Panics if `pos >= N`
+    pub fn get(&self, pos: usize) -> &T {
+        &self.set[self.idxs[pos]]
+    }
+    /// Returns a mutable reference to the `pos`-th selected item.
+    /// 
+    /// # Panics
+    /// Panics if `pos >= N`
+    pub fn get_mut(&mut self, pos: usize) -> &mut T {
+        &mut self.set[self.idxs[pos]]
+    }
+    /// Returns an array of mutable references to selected items.
+    pub fn as_array_mut(&mut self) -> [&mut T; N] {
+        let ptr = self.set.as_mut_ptr();
+        self.idxs.map(|idx| unsafe{&mut *ptr.add(idx)})
+    }
+    /// Converts to an array of mutable references to selected items.
+    pub fn into_array(self) -> [&'a mut T; N] {
+        let ptr = self.set.as_mut_ptr();
+        self.idxs.map(|idx| unsafe{&mut *ptr.add(idx)})
+    }
+    /// Returns an iterator over immutable references to selected items.
+    pub fn iter(&self) -> multi::Iter<'_, T> {
+        multi::Iter {
+            ptr: self.set.as_ptr(),
+            iter: self.idxs.iter()
+        }
+    }
+    /// Returns an iterator over mutable references to selected items.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            ptr: self.set.as_mut_ptr(),
+            iter: self.idxs.iter()
+        }
+    }
+}
+
+
+impl<'a, T, const N: usize> From<FixedSubsetMut<'a, T, N>> for SubsetMut<'a, T> {
+    fn from(s: FixedSubsetMut<'a, T, N>) -> Self {
+        SubsetMut {
+            m: multi::SubsetMut {
+                set: s.set,
+                idxs: Cow::Owned(s.idxs.to_vec())
+            }
+        }
+    }
+}
+
+
+impl<'a, T, const N: usize> IntoIterator for &'a FixedSubsetMut<'a, T, N> {
+    type Item = &'a T;
+    type IntoIter = multi::Iter<'a, T>;
+    fn into_iter(self) -> multi::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut FixedSubsetMut<'a, T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fixed() {
+        let mut set = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        assert_eq!(FixedSubsetMut::new(&mut set, [2, 3, 2]).err(), Some(SubsetError::NotUnique));
+        assert_eq!(FixedSubsetMut::new(&mut set, [2, 10]).err(), Some(SubsetError::OutOfBounds));
+        let mut subset = FixedSubsetMut::new(&mut set, [7, 2, 4]).unwrap();
+        assert_eq!(subset.iter().rev().copied().collect::<Vec<_>>(), vec![5, 7, 2]);
+        let [a, b, c] = subset.as_array_mut();
+        std::mem::swap(a, c);
+        *b += 10;
+        assert_eq!(subset.set(), vec![9, 8, 17, 6, 2, 4, 3, 5, 1, 0].as_slice());
+        let subset: SubsetMut<_> = subset.into();
+        assert_eq!(subset.idxs(), &[7, 2, 4]);
+        let empty = get_many_mut::<_, 0>(&mut set, []).unwrap();
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! Unique subsets over index iterators that are checked while iterating.
+
+use super::{ErrorPolicy, SubsetError};
+use std::marker::PhantomData;
+
+
+/// Subset of slice's items selected by an iterator over indexes, without collecting them,
+/// that yields mutable references to selected items.
+/// Both the array bounds and the uniqueness is checked for every index when it is reached,
+/// the latter against a bitmap of already selected items.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut set = [1, 2, 3, 4, 5, 6];
+/// let evens = set.iter().enumerate().filter(|(_, v)| **v % 2 == 0).map(|(idx, _)| idx).collect::<Vec<_>>();
+/// let mut errors = Vec::new();
+/// for v in LazySubsetMut::new(&mut set, evens.iter().copied().chain([1, 9])) {
+///     match v {
+///         Ok(v) => *v *= 10,
+///         Err(e) => errors.push(e)
+///     }
+/// }
+/// assert_eq!(set, [1, 20, 3, 40, 5, 60]);
+/// assert_eq!(errors, vec![SubsetError::NotUnique, SubsetError::OutOfBounds]);
+/// 
+/// let subset = LazySubsetMut::with_policy(&mut set, [0, 2, 0, 4], ErrorPolicy::Stop);
+/// assert_eq!(subset.map(|v| v.map(|v| *v)).collect::<Vec<_>>(), vec![Ok(1), Ok(3), Err(SubsetError::NotUnique)]);
+/// ```
+pub struct LazySubsetMut<'a, T, I> {
+    ptr: *mut T,    // Points to the set
+    len: usize,
+    idxs: I,
+    selected: Vec<u64>,    // Bitmap of already selected items
+    policy: ErrorPolicy,
+    stopped: bool,
+    _marker: PhantomData<&'a mut [T]>
+}
+
+
+impl<'a, T, I: Iterator<Item = usize>> LazySubsetMut<'a, T, I> {
+    /// Constructs a subset from the whole set and an iterator over indexes of the selected items.
+    /// Indexes out of bounds or selected twice yield errors and are skipped.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new<J: IntoIterator<IntoIter = I>>(set: &'a mut [T], idxs: J) -> Self {
+        Self::with_policy(set, idxs, ErrorPolicy::Yield)
+    }
+    /// Constructs a subset from the whole set and an iterator over indexes of the selected items
+    /// that treats indexes out of bounds or selected twice according to `policy`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn with_policy<J: IntoIterator<IntoIter = I>>(set: &'a mut [T], idxs: J, policy: ErrorPolicy) -> Self {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        Self {
+            ptr: set.as_mut_ptr(),
+            len: set.len(),
+            idxs: idxs.into_iter(),
+            selected: vec![0; set.len().div_ceil(64)],
+            policy,
+            stopped: false,
+            _marker: PhantomData
+        }
+    }
+    /// Returns the policy for indexes out of bounds or selected twice.
+    pub fn policy(&self) -> ErrorPolicy {
+        self.policy
+    }
+    /// Marks `idx` as selected.
+    fn select(&mut self, idx: usize) -> Result<(), SubsetError> {
+        if idx >= self.len {
+            return Err(SubsetError::OutOfBounds);
+        }
+        let (word, bit) = (idx / 64, 1 << (idx % 64));
+        if self.selected[word] & bit != 0 {
+            return Err(SubsetError::NotUnique);
+        }
+        self.selected[word] |= bit;
+        Ok(())
+    }
+}
+
+
+impl<'a, T: 'a, I: Iterator<Item = usize>> Iterator for LazySubsetMut<'a, T, I> {
+    type Item = Result<&'a mut T, SubsetError>;
+    fn next(&mut self) -> Option<Result<&'a mut T, SubsetError>> {
+        if self.stopped {
+            return None;
+        }
+        loop {
+            let idx = self.idxs.next()?;
+            match self.select(idx) {
+                // Every item is yielded no more than once, so mutable references never alias
+                Ok(()) => return Some(Ok(unsafe{&mut *self.ptr.add(idx)})),
+                Err(e) => match self.policy {
+                    ErrorPolicy::Yield => return Some(Err(e)),
+                    ErrorPolicy::Stop => {
+                        self.stopped = true;
+                        return Some(Err(e));
+                    }
+                    ErrorPolicy::Skip => continue
+                }
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.stopped {
+            return (0, Some(0));
+        }
+        match self.policy {
+            ErrorPolicy::Skip => (0, self.idxs.size_hint().1),
+            _ => self.idxs.size_hint()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_lazy_mut() {
+        let mut set = vec![0; 70];
+        let idxs = [69, 3, 70, 69, 5];
+        let results: Vec<_> = LazySubsetMut::new(&mut set, idxs.iter().copied()).map(|v| v.map(|v| *v = 1)).collect();
+        assert_eq!(results, vec![Ok(()), Ok(()), Err(SubsetError::OutOfBounds), Err(SubsetError::NotUnique), Ok(())]);
+        assert_eq!(set.iter().sum::<i32>(), 3);
+        let stopped = LazySubsetMut::with_policy(&mut set, idxs.iter().copied(), ErrorPolicy::Stop);
+        assert_eq!(stopped.count(), 3);
+        let mut skipped = LazySubsetMut::with_policy(&mut set, idxs.iter().copied(), ErrorPolicy::Skip);
+        let a = skipped.next().unwrap().unwrap();
+        let b = skipped.next().unwrap().unwrap();
+        std::mem::swap(a, b);
+        *skipped.next().unwrap().unwrap() = 7;
+        assert!(skipped.next().is_none());
+        assert_eq!(set[5], 7);
+    }
+}
@@ -39,15 +39,40 @@
 //! ```
 
 pub use std::convert::{From,Into,TryFrom,TryInto};
-use super::{is_unique, multi};
-pub use super::SubsetError;
+use super::{is_unique, is_unique_pairwise, multi, Idx};
+pub use super::{ErrorPolicy, SubsetError};
+
+mod address;
+mod checker;
+mod complement;
+mod cursor;
+mod fixed;
+mod lazy;
+mod permutation;
+mod remove;
+mod reorder;
+mod swap;
+mod validated;
+mod windows;
+mod zip;
+pub use address::*;
+pub use checker::*;
+pub use complement::*;
+pub use cursor::*;
+pub use fixed::*;
+pub use lazy::*;
+pub use permutation::Permutation;
+pub use remove::*;
+pub use swap::*;
+pub use validated::*;
+pub use windows::*;
+pub use zip::*;
 
 /// Subset of slice's items that is able to iterate forward and backward over immutable references to selected items.
 /// Each item of a slice can be selected no more than once.
 // Just a wrapper over multi::Subset
-#[derive(Debug)]
-pub struct Subset<'a, T> {
-    pub(crate) m: multi::Subset<'a, T>
+pub struct Subset<'a, T, I: Idx = usize> {
+    pub(crate) m: multi::Subset<'a, T, I>
 }
 
 
@@ -72,30 +97,82 @@ impl<'a, T> Subset<'a, T> {
     /// # Panics
     /// Panics if `std::mem::size_of::<T>() == 0`
     pub fn new(set: &'a [T], idxs: &'a [usize]) -> Result<Self, SubsetError> {
-        multi::Subset::new(set, idxs)?.try_into()
+        Self::new_typed(set, idxs)
     }
     /// Constructs a subset from the whole set and indexes of the selected items.
     /// Neither the uniqueness of the selected items, nor the array bounds is checked.
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()` and no index may occur twice.
     pub unsafe fn new_unchecked(set: &'a [T], idxs: &'a [usize]) -> Self {
-        multi::Subset::new_unchecked(set, idxs).to_unique_unchecked()
+        Self::new_typed_unchecked(set, idxs)
+    }
+    /// Constructs a subset that owns indexes of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_vec(set: &'a [T], idxs: Vec<usize>) -> Result<Self, SubsetError> {
+        Self::from_typed_vec(set, idxs)
+    }
+}
+
+
+impl<'a, T, I: Idx> Subset<'a, T, I> {
+    /// Constructs a subset from the whole set and indexes of any `Idx` type of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked.
+    /// Note that subsets are not designed for ZSTs.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new_typed(set: &'a [T], idxs: &'a [I]) -> Result<Self, SubsetError> {
+        multi::Subset::new_typed(set, idxs)?.try_into()
+    }
+    /// Constructs a subset from the whole set and indexes of any `Idx` type of the selected items.
+    /// Neither the uniqueness of the selected items, nor the array bounds is checked.
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()` and no index may occur twice.
+    pub unsafe fn new_typed_unchecked(set: &'a [T], idxs: &'a [I]) -> Self {
+        multi::Subset::new_typed_unchecked(set, idxs).to_unique_unchecked()
+    }
+    /// Constructs a subset that owns indexes of any `Idx` type of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_typed_vec(set: &'a [T], idxs: Vec<I>) -> Result<Self, SubsetError> {
+        multi::Subset::from_typed_vec(set, idxs)?.try_into()
     }
     /// Returns the original slice.
     pub fn set(&self) -> &[T] {
         self.m.set()
     }
     /// Returns indexes of selected items.
-    pub fn idxs(&self) -> &[usize] {
+    pub fn idxs(&self) -> &[I] {
         self.m.idxs()
     }
     /// Returns an iterator over immutable references to selected items.
-    pub fn iter(&self) -> multi::Iter<T> {
+    pub fn iter(&self) -> multi::Iter<'_, T, I> {
         self.m.iter()
     }
 }
 
 
-impl<'a, T> From<SubsetMut<'a, T>> for Subset<'a, T> {
-    fn from(s: SubsetMut<'a, T>) -> Self {
+impl<'a, T, I: Idx> From<SubsetMut<'a, T, I>> for Subset<'a, T, I> {
+    fn from(s: SubsetMut<'a, T, I>) -> Self {
         Self {
             m: s.m.into()
         }
@@ -103,10 +180,10 @@ impl<'a, T> From<SubsetMut<'a, T>> for Subset<'a, T> {
 }
 
 
-impl<'a, T> TryFrom<multi::Subset<'a, T>> for Subset<'a, T> {
+impl<'a, T, I: Idx> TryFrom<multi::Subset<'a, T, I>> for Subset<'a, T, I> {
     type Error = SubsetError;
-    fn try_from(s: multi::Subset<'a, T>) -> Result<Self, SubsetError> {
-        if is_unique(s.idxs) {
+    fn try_from(s: multi::Subset<'a, T, I>) -> Result<Self, SubsetError> {
+        if is_unique(&s.idxs) {
             Ok(unsafe{s.to_unique_unchecked()})
         } else {
             Err(SubsetError::NotUnique)
@@ -115,10 +192,10 @@ impl<'a, T> TryFrom<multi::Subset<'a, T>> for Subset<'a, T> {
 }
 
 
-impl<'a, T> TryFrom<multi::SubsetMut<'a, T>> for Subset<'a, T> {
+impl<'a, T, I: Idx> TryFrom<multi::SubsetMut<'a, T, I>> for Subset<'a, T, I> {
     type Error = SubsetError;
-    fn try_from(s: multi::SubsetMut<'a, T>) -> Result<Self, SubsetError> {
-        if is_unique(s.idxs) {
+    fn try_from(s: multi::SubsetMut<'a, T, I>) -> Result<Self, SubsetError> {
+        if is_unique(&s.idxs) {
             Ok(unsafe{s.to_unique_unchecked()})
         } else {
             Err(SubsetError::NotUnique)
@@ -127,10 +204,10 @@ impl<'a, T> TryFrom<multi::SubsetMut<'a, T>> for Subset<'a, T> {
 }
 
 
-impl<'a, T> IntoIterator for &'a Subset<'a, T> {
+impl<'a, T, I: Idx> IntoIterator for &'a Subset<'a, T, I> {
     type Item = &'a T;
-    type IntoIter = multi::Iter<'a, T>;
-    fn into_iter(self) -> multi::Iter<'a, T> {
+    type IntoIter = multi::Iter<'a, T, I>;
+    fn into_iter(self) -> multi::Iter<'a, T, I> {
         self.iter()
     }
 }
@@ -139,15 +216,14 @@ impl<'a, T> IntoIterator for &'a Subset<'a, T> {
 /// Subset of slice's items that is able to iterate forward and backward over mutable or immutable references to selected items.
 /// Each item of a slice can be selected no more than once.
 // Just a wrapper over multi::SubsetMut
-#[derive(Debug)]
-pub struct SubsetMut<'a, T> {
-    pub(crate) m: multi::SubsetMut<'a, T>
+pub struct SubsetMut<'a, T, I: Idx = usize> {
+    pub(crate) m: multi::SubsetMut<'a, T, I>
 }
 
 /// Double-ended iterator over mutable references to selected items of set.
-pub struct IterMut<'a, T> {
+pub struct IterMut<'a, T, I = usize> {
     ptr: *mut T,    // Points to the set
-    iter: std::slice::Iter<'a, usize>
+    iter: std::slice::Iter<'a, I>
 }
 
 impl<'a, T> SubsetMut<'a, T> {
@@ -172,27 +248,92 @@ impl<'a, T> SubsetMut<'a, T> {
     /// # Panics
     /// Panics if `std::mem::size_of::<T>() == 0`
     pub fn new(set: &'a mut [T], idxs: &'a [usize]) -> Result<Self, SubsetError> {
-        multi::SubsetMut::new(set, idxs)?.try_into()
+        Self::new_typed(set, idxs)
     }
     /// Constructs a subset from the whole set and indexes of the selected items.
     /// Neither the uniqueness of the selected items, nor the array bounds is checked.
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()` and no index may occur twice.
     pub unsafe fn new_unchecked(set: &'a mut [T], idxs: &'a [usize]) -> Self {
-        multi::SubsetMut::new_unchecked(set, idxs).to_unique_mut_unchecked()
+        Self::new_typed_unchecked(set, idxs)
+    }
+    /// Constructs a subset that owns indexes of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_vec(set: &'a mut [T], idxs: Vec<usize>) -> Result<Self, SubsetError> {
+        Self::from_typed_vec(set, idxs)
+    }
+}
+
+
+impl<'a, T, I: Idx> SubsetMut<'a, T, I> {
+    /// Constructs a subset from the whole set and indexes of any `Idx` type of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked.
+    /// Note that subsets are not designed for ZSTs.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn new_typed(set: &'a mut [T], idxs: &'a [I]) -> Result<Self, SubsetError> {
+        multi::SubsetMut::new_typed(set, idxs)?.try_into()
+    }
+    /// Constructs a subset from the whole set and indexes of any `Idx` type of the selected items.
+    /// Neither the uniqueness of the selected items, nor the array bounds is checked.
+    /// 
+    /// # Safety
+    /// Every index must be `< set.len()` and no index may occur twice.
+    pub unsafe fn new_typed_unchecked(set: &'a mut [T], idxs: &'a [I]) -> Self {
+        multi::SubsetMut::new_typed_unchecked(set, idxs).to_unique_mut_unchecked()
+    }
+    /// Constructs a subset that owns indexes of any `Idx` type of the selected items.
+    /// Both the uniqueness of the selected items and the array bounds is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= set.len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_typed_vec(set: &'a mut [T], idxs: Vec<I>) -> Result<Self, SubsetError> {
+        multi::SubsetMut::from_typed_vec(set, idxs)?.try_into()
     }
     /// Returns the original slice.
     pub fn set(&mut self) -> &mut [T] {
         self.m.set()
     }
     /// Returns indexes of selected items.
-    pub fn idxs(&self) -> &[usize] {
+    pub fn idxs(&self) -> &[I] {
         self.m.idxs()
     }
     /// Returns an iterator over immutable references to selected items.
-    pub fn iter(&self) -> multi::Iter<T> {
+    pub fn iter(&self) -> multi::Iter<'_, T, I> {
         self.m.iter()
     }
     /// Returns an iterator over mutable references to selected items.
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    /// 
+    /// The original slice cannot be accessed while the yielded references are alive:
+    /// 
+    /// ```compile_fail
+    /// # use subset::unique::*;
+    /// let mut set = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+    /// let idxs = vec![2, 4, 7];
+    /// let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+    /// let mut iter = subset.iter_mut();
+    /// let r2 = iter.next().unwrap();
+    /// subset.set()[0] = 100;
+    /// *r2 = 19;
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, I> {
         IterMut {
             ptr: self.m.set.as_mut_ptr(),
             iter: self.m.idxs.iter()
@@ -201,10 +342,10 @@ impl<'a, T> SubsetMut<'a, T> {
 }
 
 
-impl<'a, T> TryFrom<multi::SubsetMut<'a, T>> for SubsetMut<'a, T> {
+impl<'a, T, I: Idx> TryFrom<multi::SubsetMut<'a, T, I>> for SubsetMut<'a, T, I> {
     type Error = SubsetError;
-    fn try_from(s: multi::SubsetMut<'a, T>) -> Result<Self, SubsetError> {
-        if is_unique(s.idxs) {
+    fn try_from(s: multi::SubsetMut<'a, T, I>) -> Result<Self, SubsetError> {
+        if is_unique(&s.idxs) {
             Ok(unsafe{s.to_unique_mut_unchecked()})
         } else {
             Err(SubsetError::NotUnique)
@@ -213,44 +354,50 @@ impl<'a, T> TryFrom<multi::SubsetMut<'a, T>> for SubsetMut<'a, T> {
 }
 
 
-impl<'a, T: 'a> Iterator for IterMut<'a, T> {
+impl<'a, T: 'a, I: Idx> Iterator for IterMut<'a, T, I> {
     type Item = &'a mut T;
     fn next(&mut self) -> Option<&'a mut T> {
         unsafe {
             match self.iter.next() {
                 None => None,
-                Some(idx) => Some(&mut *self.ptr.offset(*idx as isize))
+                Some(idx) => Some(&mut *self.ptr.add(idx.index()))
             }
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
 
-impl<'a, T: 'a> DoubleEndedIterator for IterMut<'a, T> {
+impl<'a, T: 'a, I: Idx> ExactSizeIterator for IterMut<'a, T, I> {}
+
+
+impl<'a, T: 'a, I: Idx> DoubleEndedIterator for IterMut<'a, T, I> {
     fn next_back(&mut self) -> Option<&'a mut T> {
         unsafe {
             match self.iter.next_back() {
                 None => None,
-                Some(idx) => Some(&mut *self.ptr.offset(*idx as isize))
+                Some(idx) => Some(&mut *self.ptr.add(idx.index()))
             }
         }
     }
 }
 
 
-impl<'a, T> IntoIterator for &'a SubsetMut<'a, T> {
+impl<'a, T, I: Idx> IntoIterator for &'a SubsetMut<'a, T, I> {
     type Item = &'a T;
-    type IntoIter = multi::Iter<'a, T>;
-    fn into_iter(self) -> multi::Iter<'a, T> {
+    type IntoIter = multi::Iter<'a, T, I>;
+    fn into_iter(self) -> multi::Iter<'a, T, I> {
         self.iter()
     }
 }
 
 
-impl<'a, T> IntoIterator for &'a mut SubsetMut<'a, T> {
+impl<'a, T, I: Idx> IntoIterator for &'a mut SubsetMut<'a, T, I> {
     type Item = &'a mut T;
-    type IntoIter = IterMut<'a, T>;
-    fn into_iter(self) -> IterMut<'a, T> {
+    type IntoIter = IterMut<'a, T, I>;
+    fn into_iter(self) -> IterMut<'a, T, I> {
         self.iter_mut()
     }
 }
@@ -0,0 +1,181 @@
+//! Permutations, i.e. unique subsets covering the whole slice.
+
+use super::{is_unique, multi, SubsetError, Subset};
+
+
+/// Permutation of `0..len`.
+/// 
+/// Applying it to a slice moves the item at position `idxs()[i]` to position `i`,
+/// so the permuted slice looks like a `unique::Subset` selecting all items in `idxs()` order.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut names = ["carol", "alice", "bob"];
+/// let mut ages = [35, 30, 25];
+/// let perm = Permutation::argsort(&names);
+/// perm.apply(&mut names);
+/// perm.apply(&mut ages);
+/// assert_eq!(names, ["alice", "bob", "carol"]);
+/// assert_eq!(ages, [30, 25, 35]);
+/// perm.inverse().apply(&mut ages);
+/// assert_eq!(ages, [35, 30, 25]);
+/// ```
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+pub struct Permutation {
+    idxs: Vec<usize>
+}
+
+
+impl Permutation {
+    /// Constructs a permutation from indexes which must be a rearrangement of `0..idxs.len()`.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= idxs.len()`.
+    pub fn new(idxs: Vec<usize>) -> Result<Self, SubsetError> {
+        let len = idxs.len();
+        if idxs.iter().any(|v| *v >= len) {
+            Err(SubsetError::OutOfBounds)
+        } else if !is_unique(&idxs) {
+            Err(SubsetError::NotUnique)
+        } else { Ok(Self { idxs }) }
+    }
+    /// Constructs the identity permutation of `0..len`.
+    pub fn identity(len: usize) -> Self {
+        Self { idxs: (0..len).collect() }
+    }
+    /// Constructs the permutation that stably sorts `set`.
+    pub fn argsort<T: Ord>(set: &[T]) -> Self {
+        let mut idxs: Vec<usize> = (0..set.len()).collect();
+        idxs.sort_by(|a, b| set[*a].cmp(&set[*b]));
+        Self { idxs }
+    }
+    /// Constructs the permutation that stably sorts `set` by the key extracted with `f`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let set = [-3i32, 1, -2, 0];
+    /// assert_eq!(Permutation::argsort_by_key(&set, |v| v.abs()).idxs(), &[3, 1, 2, 0]);
+    /// ```
+    pub fn argsort_by_key<T, K: Ord, F: FnMut(&T) -> K>(set: &[T], mut f: F) -> Self {
+        let mut idxs: Vec<usize> = (0..set.len()).collect();
+        idxs.sort_by_key(|idx| f(&set[*idx]));
+        Self { idxs }
+    }
+    /// Returns the permuted positions.
+    pub fn idxs(&self) -> &[usize] {
+        &self.idxs
+    }
+    /// Returns the length of slices the permutation applies to.
+    pub fn len(&self) -> usize {
+        self.idxs.len()
+    }
+    /// Checks whether the permutation is empty.
+    pub fn is_empty(&self) -> bool {
+        self.idxs.is_empty()
+    }
+    /// Converts back to the index list.
+    pub fn into_vec(self) -> Vec<usize> {
+        self.idxs
+    }
+    /// Checks whether applying the permutation leaves any slice unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.idxs.iter().enumerate().all(|(pos, idx)| pos == *idx)
+    }
+    /// Returns the permutation that undoes this one.
+    pub fn inverse(&self) -> Self {
+        let mut idxs = vec![0; self.idxs.len()];
+        for (pos, idx) in self.idxs.iter().enumerate() {
+            idxs[*idx] = pos;
+        }
+        Self { idxs }
+    }
+    /// Returns the permutation equivalent to applying `self` and then `other`.
+    /// 
+    /// # Panics
+    /// Panics if `self.len() != other.len()`
+    pub fn compose(&self, other: &Self) -> Self {
+        assert_eq!(self.len(), other.len());
+        Self { idxs: other.idxs.iter().map(|idx| self.idxs[*idx]).collect() }
+    }
+    /// Returns a subset that selects all items of `set` in permuted order.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if `set.len() != self.len()`.
+    pub fn subset<'a, T>(&'a self, set: &'a [T]) -> Result<Subset<'a, T>, SubsetError> {
+        if set.len() != self.len() {
+            Err(SubsetError::OutOfBounds)
+        } else { Ok(unsafe{multi::Subset::new_unchecked(set, &self.idxs).to_unique_unchecked()}) }
+    }
+    /// Permutes `set` in place by following cycles of the permutation.
+    /// Only a bitmap of visited positions is allocated.
+    /// 
+    /// # Panics
+    /// Panics if `set.len() != self.len()`
+    pub fn apply<T>(&self, set: &mut [T]) {
+        assert_eq!(set.len(), self.len());
+        permute(set, &self.idxs);
+    }
+    /// Returns a permuted copy of `set`.
+    /// 
+    /// # Panics
+    /// Panics if `set.len() != self.len()`
+    pub fn apply_to_copy<T: Clone>(&self, set: &[T]) -> Vec<T> {
+        assert_eq!(set.len(), self.len());
+        self.idxs.iter().map(|idx| set[*idx].clone()).collect()
+    }
+}
+
+
+/// Reorders `set` in place so that the item at position `i` is the one that was at `perm[i]`.
+/// `perm` must be a permutation of `0..set.len()`.
+pub(crate) fn permute<T>(set: &mut [T], perm: &[usize]) {
+    let mut visited = vec![false; set.len()];
+    for start in 0..set.len() {
+        if visited[start] {
+            continue;
+        }
+        // The item from `start` is carried along the cycle until its destination
+        let mut pos = start;
+        loop {
+            visited[pos] = true;
+            let src = perm[pos];
+            if src == start {
+                break;
+            }
+            set.swap(pos, src);
+            pos = src;
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_permutation() {
+        assert_eq!(Permutation::new(vec![0, 3, 1]).err(), Some(SubsetError::OutOfBounds));
+        assert_eq!(Permutation::new(vec![0, 2, 2]).err(), Some(SubsetError::NotUnique));
+        let p = Permutation::new(vec![2, 0, 3, 1, 4]).unwrap();
+        let q = Permutation::new(vec![4, 3, 2, 1, 0]).unwrap();
+        let set = vec!['a', 'b', 'c', 'd', 'e'];
+        let mut permuted = set.clone();
+        p.apply(&mut permuted);
+        assert_eq!(permuted, p.apply_to_copy(&set));
+        assert_eq!(permuted, vec!['c', 'a', 'd', 'b', 'e']);
+        assert_eq!(p.subset(&set).unwrap().iter().copied().collect::<Vec<_>>(), permuted);
+        q.apply(&mut permuted);
+        assert_eq!(permuted, p.compose(&q).apply_to_copy(&set));
+        assert!(p.compose(&p.inverse()).is_identity());
+        assert!(!p.is_identity());
+        assert!(Permutation::identity(3).is_identity());
+        assert_eq!(Permutation::argsort(&[3, 1, 2, 1]).idxs(), &[1, 3, 2, 0]);
+    }
+}
@@ -0,0 +1,143 @@
+//! Removal of selected items from vectors.
+
+use super::{SubsetError, SubsetMut};
+use std::ptr;
+
+
+/// Removes selected items from the vector and returns them in selection order.
+/// The relative order of the remaining items is preserved.
+/// Takes O(n) time.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut set = vec![9, 8, 7, 6, 5, 4];
+/// assert_eq!(drain_subset(&mut set, &[4, 0, 2]), Ok(vec![5, 9, 7]));
+/// assert_eq!(set, vec![8, 6, 4]);
+/// ```
+/// 
+/// # Errors
+/// NotUnique, if there are duplicate indexes.
+/// OutOfBounds, if any index is `>= vec.len()`.
+/// 
+/// # Panics
+/// Panics if `std::mem::size_of::<T>() == 0`
+pub fn drain_subset<T>(vec: &mut Vec<T>, idxs: &[usize]) -> Result<Vec<T>, SubsetError> {
+    SubsetMut::new(vec, idxs)?;
+    let mut drained = Vec::with_capacity(idxs.len());
+    let ptr = vec.as_ptr();
+    drained.extend(idxs.iter().map(|idx| unsafe{ptr::read(ptr.add(*idx))}));
+    // Drained items are already moved out, so they must not be dropped again
+    unsafe{compact(vec, idxs, std::mem::forget)};
+    Ok(drained)
+}
+
+
+/// Removes and drops selected items.
+/// The relative order of the remaining items is preserved.
+/// Takes O(n) time.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut set = vec![9, 8, 7, 6, 5, 4];
+/// remove_subset(&mut set, &[4, 0, 2]).unwrap();
+/// assert_eq!(set, vec![8, 6, 4]);
+/// ```
+/// 
+/// # Errors
+/// NotUnique, if there are duplicate indexes.
+/// OutOfBounds, if any index is `>= vec.len()`.
+/// 
+/// # Panics
+/// Panics if `std::mem::size_of::<T>() == 0`
+pub fn remove_subset<T>(vec: &mut Vec<T>, idxs: &[usize]) -> Result<(), SubsetError> {
+    SubsetMut::new(vec, idxs)?;
+    unsafe{compact(vec, idxs, drop)};
+    Ok(())
+}
+
+
+/// Removes and drops selected items, filling the gaps with items from the end of the vector.
+/// The order of the remaining items is not preserved.
+/// Takes O(k log k) time, where k is the number of selected items.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut set = vec![9, 8, 7, 6, 5, 4];
+/// swap_remove_subset(&mut set, &[0, 2]).unwrap();
+/// assert_eq!(set, vec![5, 8, 4, 6]);
+/// ```
+/// 
+/// # Errors
+/// NotUnique, if there are duplicate indexes.
+/// OutOfBounds, if any index is `>= vec.len()`.
+/// 
+/// # Panics
+/// Panics if `std::mem::size_of::<T>() == 0`
+pub fn swap_remove_subset<T>(vec: &mut Vec<T>, idxs: &[usize]) -> Result<(), SubsetError> {
+    SubsetMut::new(vec, idxs)?;
+    let mut sorted = idxs.to_vec();
+    sorted.sort_unstable();
+    // In descending order the last item is never a selected one
+    for idx in sorted.into_iter().rev() {
+        vec.swap_remove(idx);
+    }
+    Ok(())
+}
+
+
+// Moves unselected items to the front, passing selected ones to `removed` in set order.
+// Safety: `idxs` are unique and in bounds.
+unsafe fn compact<T>(vec: &mut Vec<T>, idxs: &[usize], mut removed: impl FnMut(T)) {
+    let len = vec.len();
+    let mut selected = vec![false; len];
+    for idx in idxs {
+        selected[*idx] = true;
+    }
+    // If `removed` panics, the remaining items are leaked instead of being dropped twice
+    vec.set_len(0);
+    let ptr = vec.as_mut_ptr();
+    let mut kept = 0;
+    for (idx, is_selected) in selected.into_iter().enumerate() {
+        if is_selected {
+            removed(ptr::read(ptr.add(idx)));
+        } else {
+            ptr::copy(ptr.add(idx), ptr.add(kept), 1);
+            kept += 1;
+        }
+    }
+    vec.set_len(kept);
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_remove() {
+        let items: Vec<Rc<i32>> = (0..10).map(Rc::new).collect();
+        let mut set = items.clone();
+        assert_eq!(drain_subset(&mut set, &[2, 2]).err(), Some(SubsetError::NotUnique));
+        assert_eq!(remove_subset(&mut set, &[10]).err(), Some(SubsetError::OutOfBounds));
+        let drained = drain_subset(&mut set, &[7, 1, 8]).unwrap();
+        assert_eq!(drained.iter().map(|v| **v).collect::<Vec<_>>(), vec![7, 1, 8]);
+        remove_subset(&mut set, &[0, 5]).unwrap();
+        assert_eq!(set.iter().map(|v| **v).collect::<Vec<_>>(), vec![2, 3, 4, 5, 9]);
+        assert_eq!(Rc::strong_count(&items[6]), 1);
+        assert_eq!(Rc::strong_count(&items[7]), 2);
+        swap_remove_subset(&mut set, &[4, 0, 1]).unwrap();
+        assert_eq!(set.iter().map(|v| **v).collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(Rc::strong_count(&items[9]), 1);
+        drop(set);
+        drop(drained);
+        assert!(items.iter().all(|v| Rc::strong_count(v) == 1));
+    }
+}
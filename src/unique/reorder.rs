@@ -0,0 +1,102 @@
+//! Stable partition of a slice by a unique subset.
+
+use super::permutation::permute;
+use super::{Permutation, SubsetMut};
+use std::borrow::Cow;
+
+
+impl<'a, T> SubsetMut<'a, T> {
+    /// Stably reorders the set so that selected items come first, in selection order,
+    /// followed by the other items in their original order.
+    /// Afterwards the subset selects the items at `0..len`.
+    /// 
+    /// Returns the permutation applied: the item at position `i` is the one that was at `perm.idxs()[i]`,
+    /// so other parallel arrays can be reordered identically with `Permutation::apply`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let mut set = ['a', 'b', 'c', 'd', 'e'];
+    /// let idxs = [3, 1];
+    /// let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+    /// let perm = subset.move_to_front();
+    /// assert_eq!(subset.idxs(), &[0, 1]);
+    /// assert_eq!(perm.idxs(), &[3, 1, 0, 2, 4]);
+    /// assert_eq!(set, ['d', 'b', 'a', 'c', 'e']);
+    /// let mut ids = [10, 11, 12, 13, 14];
+    /// perm.apply(&mut ids);
+    /// assert_eq!(ids, [13, 11, 10, 12, 14]);
+    /// ```
+    pub fn move_to_front(&mut self) -> Permutation {
+        let mut perm = self.m.idxs.to_vec();
+        perm.extend(self.unselected());
+        permute(self.m.set, &perm);
+        self.m.idxs = Cow::Owned((0..self.m.idxs.len()).collect());
+        // Selected indexes are unique and in bounds, unselected ones are the rest of `0..set.len()`
+        Permutation::new(perm).expect("selected and unselected indexes cover the set")
+    }
+    /// Stably reorders the set so that selected items come last, in selection order,
+    /// preceded by the other items in their original order.
+    /// Afterwards the subset selects the items at `set.len() - len..set.len()`.
+    /// 
+    /// Returns the permutation applied: the item at position `i` is the one that was at `perm.idxs()[i]`,
+    /// so other parallel arrays can be reordered identically with `Permutation::apply`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let mut set = ['a', 'b', 'c', 'd', 'e'];
+    /// let idxs = [3, 1];
+    /// let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+    /// let perm = subset.move_to_back();
+    /// assert_eq!(subset.idxs(), &[3, 4]);
+    /// assert_eq!(perm.idxs(), &[0, 2, 4, 3, 1]);
+    /// assert_eq!(set, ['a', 'c', 'e', 'd', 'b']);
+    /// ```
+    pub fn move_to_back(&mut self) -> Permutation {
+        let mut perm = self.unselected();
+        perm.extend_from_slice(&self.m.idxs);
+        permute(self.m.set, &perm);
+        let set_len = self.m.set.len();
+        self.m.idxs = Cow::Owned((set_len - self.m.idxs.len()..set_len).collect());
+        Permutation::new(perm).expect("selected and unselected indexes cover the set")
+    }
+    // Indexes of unselected items in ascending order
+    fn unselected(&self) -> Vec<usize> {
+        let mut selected = vec![false; self.m.set.len()];
+        for idx in self.m.idxs.iter() {
+            selected[*idx] = true;
+        }
+        selected.into_iter().enumerate()
+            .filter(|(_, is_selected)| !is_selected)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_reorder() {
+        let mut set: Vec<usize> = (0..10).collect();
+        let mut mass: Vec<usize> = (0..10).map(|v| v * 100).collect();
+        let idxs = vec![7, 2, 9, 4];
+        let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+        let perm = subset.move_to_front();
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![7, 2, 9, 4]);
+        assert_eq!(set, vec![7, 2, 9, 4, 0, 1, 3, 5, 6, 8]);
+        perm.apply(&mut mass);
+        assert_eq!(mass, set.iter().map(|v| v * 100).collect::<Vec<_>>());
+        let idxs = vec![0, 1];
+        let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+        subset.move_to_back();
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![7, 2]);
+        assert_eq!(set, vec![9, 4, 0, 1, 3, 5, 6, 8, 7, 2]);
+    }
+}
@@ -0,0 +1,86 @@
+//! Swapping contents of disjoint unique subsets.
+
+use super::{SubsetError, SubsetMut};
+
+
+/// Swaps `set[a[k]]` with `set[b[k]]` for every `k`.
+/// Each index list must be unique and the two lists must be disjoint.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut set = [0, 1, 2, 3, 4, 5];
+/// swap_subsets(&mut set, &[0, 2, 4], &[1, 3, 5]).unwrap();
+/// assert_eq!(set, [1, 0, 3, 2, 5, 4]);
+/// assert_eq!(swap_subsets(&mut set, &[0, 2], &[1, 2]).err(), Some(SubsetError::NotUnique));
+/// ```
+/// 
+/// # Errors
+/// NotUnique, if there are duplicate indexes within or across the lists.
+/// OutOfBounds, if any index is `>= set.len()`.
+/// 
+/// # Panics
+/// Panics if `a.len() != b.len()` or `std::mem::size_of::<T>() == 0`
+pub fn swap_subsets<T>(set: &mut [T], a: &[usize], b: &[usize]) -> Result<(), SubsetError> {
+    assert_eq!(a.len(), b.len());
+    let both: Vec<usize> = a.iter().chain(b).copied().collect();
+    SubsetMut::new(set, &both)?;
+    for (i, j) in a.iter().zip(b) {
+        set.swap(*i, *j);
+    }
+    Ok(())
+}
+
+
+impl<'a, T> SubsetMut<'a, T> {
+    /// Swaps the `k`-th selected item of this subset with the `k`-th selected item of `other` for every `k`.
+    /// The subsets are built over different slices, so they are disjoint.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let mut front = [1, 2, 3];
+    /// let mut back = [10, 20, 30];
+    /// let (front_idxs, back_idxs) = ([0, 2], [1, 0]);
+    /// let mut a = SubsetMut::new(&mut front, &front_idxs).unwrap();
+    /// let mut b = SubsetMut::new(&mut back, &back_idxs).unwrap();
+    /// a.swap_with(&mut b);
+    /// assert_eq!(front, [20, 2, 10]);
+    /// assert_eq!(back, [3, 1, 30]);
+    /// ```
+    /// 
+    /// # Panics
+    /// Panics if the subsets select different numbers of items.
+    pub fn swap_with(&mut self, other: &mut SubsetMut<'_, T>) {
+        assert_eq!(self.idxs().len(), other.idxs().len());
+        for (x, y) in self.iter_mut().zip(other.iter_mut()) {
+            std::mem::swap(x, y);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_swap() {
+        let mut set = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        assert_eq!(swap_subsets(&mut set, &[1, 1], &[2, 3]).err(), Some(SubsetError::NotUnique));
+        assert_eq!(swap_subsets(&mut set, &[1], &[10]).err(), Some(SubsetError::OutOfBounds));
+        swap_subsets(&mut set, &[0, 1, 2], &[9, 8, 7]).unwrap();
+        assert_eq!(set, vec![0, 1, 2, 6, 5, 4, 3, 7, 8, 9]);
+        let mut other = vec![-1, -2];
+        let idxs = [5, 3];
+        let mut a = SubsetMut::new(&mut set, &idxs).unwrap();
+        let other_idxs = [1, 0];
+        let mut b = SubsetMut::new(&mut other, &other_idxs).unwrap();
+        b.swap_with(&mut a);
+        assert_eq!(set, vec![0, 1, 2, -1, 5, -2, 3, 7, 8, 9]);
+        assert_eq!(other, vec![6, 4]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Index lists that are validated once and reused for many subsets.
+
+use super::{is_unique, multi, SubsetError, Subset, SubsetMut};
+
+
+/// List of unique indexes that are all below `max_len`.
+/// 
+/// Subsets constructed from it only check that `set.len() >= max_len`,
+/// so the same index list can be reused against same-length slices without rechecking.
+/// Indexes are always `usize`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let idxs = ValidatedIdxs::new(vec![2, 4, 7], 10).unwrap();
+/// let mut set = [9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+/// for _frame in 0..3 {
+///     let mut subset = SubsetMut::from_validated(&mut set, &idxs).unwrap();
+///     for v in subset.iter_mut() {
+///         *v += 1;
+///     }
+/// }
+/// assert_eq!(set, [9, 8, 10, 6, 8, 4, 3, 5, 1, 0]);
+/// assert_eq!(SubsetMut::from_validated(&mut set[..9], &idxs).err(), Some(SubsetError::OutOfBounds));
+/// ```
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct ValidatedIdxs {
+    idxs: Vec<usize>,
+    max_len: usize
+}
+
+
+impl ValidatedIdxs {
+    /// Validates indexes against sets of length `max_len` or longer.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>= max_len`.
+    pub fn new(idxs: Vec<usize>, max_len: usize) -> Result<Self, SubsetError> {
+        if idxs.iter().any(|v| *v >= max_len) {
+            Err(SubsetError::OutOfBounds)
+        } else if !is_unique(&idxs) {
+            Err(SubsetError::NotUnique)
+        } else { Ok(Self { idxs, max_len }) }
+    }
+    /// Returns validated indexes.
+    pub fn idxs(&self) -> &[usize] {
+        &self.idxs
+    }
+    /// Returns the minimum length of sets the indexes are valid for.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+    /// Converts back to the index list.
+    pub fn into_vec(self) -> Vec<usize> {
+        self.idxs
+    }
+}
+
+
+impl<'a, T> Subset<'a, T> {
+    /// Constructs a subset from the whole set and pre-validated indexes of the selected items.
+    /// Only `set.len() >= idxs.max_len()` is checked.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if `set.len() < idxs.max_len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_validated(set: &'a [T], idxs: &'a ValidatedIdxs) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        if set.len() < idxs.max_len {
+            Err(SubsetError::OutOfBounds)
+        } else { Ok(unsafe{multi::Subset::new_unchecked(set, &idxs.idxs).to_unique_unchecked()}) }
+    }
+}
+
+
+impl<'a, T> SubsetMut<'a, T> {
+    /// Constructs a subset from the whole set and pre-validated indexes of the selected items.
+    /// Only `set.len() >= idxs.max_len()` is checked.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if `set.len() < idxs.max_len()`.
+    /// 
+    /// # Panics
+    /// Panics if `std::mem::size_of::<T>() == 0`
+    pub fn from_validated(set: &'a mut [T], idxs: &'a ValidatedIdxs) -> Result<Self, SubsetError> {
+        assert_ne!(std::mem::size_of::<T>(), 0);
+        if set.len() < idxs.max_len {
+            Err(SubsetError::OutOfBounds)
+        } else { Ok(unsafe{multi::SubsetMut::new_unchecked(set, &idxs.idxs).to_unique_mut_unchecked()}) }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_validated() {
+        assert_eq!(ValidatedIdxs::new(vec![1, 5], 5).err(), Some(SubsetError::OutOfBounds));
+        assert_eq!(ValidatedIdxs::new(vec![1, 4, 1], 5).err(), Some(SubsetError::NotUnique));
+        let idxs = ValidatedIdxs::new(vec![4, 1], 5).unwrap();
+        let set = vec![9, 8, 7, 6, 5, 4];
+        let subset = Subset::from_validated(&set, &idxs).unwrap();
+        assert_eq!(subset.iter().copied().collect::<Vec<_>>(), vec![5, 8]);
+        assert_eq!(Subset::from_validated(&set[..4], &idxs).err(), Some(SubsetError::OutOfBounds));
+        assert_eq!(idxs.into_vec(), vec![4, 1]);
+    }
+}
@@ -0,0 +1,168 @@
+//! Sliding windows over selected items, with mutable access to every item of a window.
+
+use super::{multi, Idx, Subset, SubsetMut};
+use std::marker::PhantomData;
+
+
+impl<'a, T, I: Idx> Subset<'a, T, I> {
+    /// Returns an iterator over all contiguous windows of `n` selected items, as subsets borrowing indexes of this one.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> Windows<'_, T, I> {
+        Windows {
+            m: self.m.windows(n)
+        }
+    }
+}
+
+
+impl<'a, T, I: Idx> SubsetMut<'a, T, I> {
+    /// Returns an iterator over all contiguous windows of `n` selected items, as subsets borrowing indexes of this one.
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    pub fn windows(&self, n: usize) -> Windows<'_, T, I> {
+        Windows {
+            m: self.m.windows(n)
+        }
+    }
+    /// Returns a lending iterator over all contiguous windows of `N` selected items,
+    /// that yields arrays of mutable references to items of a window.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let mut set = [1, 0, 0, 0, 9];
+    /// let idxs = [0, 2, 4];
+    /// let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+    /// let mut windows = subset.array_windows_mut::<3>();
+    /// while let Some([prev, current, next]) = windows.next() {
+    ///     *current = (*prev + *next) / 2;
+    /// }
+    /// assert_eq!(set, [1, 0, 5, 0, 9]);
+    /// ```
+    /// 
+    /// # Panics
+    /// Panics if `N == 0`
+    pub fn array_windows_mut<const N: usize>(&mut self) -> ArrayWindowsMut<'_, T, N, I> {
+        assert_ne!(N, 0, "window size must be non-zero");
+        ArrayWindowsMut {
+            ptr: self.m.set.as_mut_ptr(),
+            idxs: &self.m.idxs,
+            pos: 0,
+            _marker: PhantomData
+        }
+    }
+    /// Returns a lending iterator over all pairs of consecutive selected items,
+    /// that yields arrays of mutable references to both items of a pair.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use subset::unique::*;
+    /// let mut set = [1, 2, 3, 4];
+    /// let idxs = [3, 1, 0];
+    /// let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+    /// let mut pairs = subset.pairs_mut();
+    /// while let Some([a, b]) = pairs.next() {
+    ///     *b += *a;
+    /// }
+    /// assert_eq!(set, [7, 6, 3, 4]);
+    /// ```
+    pub fn pairs_mut(&mut self) -> ArrayWindowsMut<'_, T, 2, I> {
+        self.array_windows_mut()
+    }
+}
+
+
+/// Double-ended iterator over contiguous windows of selected items.
+pub struct Windows<'a, T, I = usize> {
+    m: multi::Windows<'a, T, I>
+}
+
+
+impl<'a, T, I: Idx> Iterator for Windows<'a, T, I> {
+    type Item = Subset<'a, T, I>;
+    fn next(&mut self) -> Option<Subset<'a, T, I>> {
+        // Windows of a unique selection are unique
+        self.m.next().map(|m| Subset { m })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.m.size_hint()
+    }
+}
+
+
+impl<'a, T, I: Idx> ExactSizeIterator for Windows<'a, T, I> {}
+
+
+impl<'a, T, I: Idx> DoubleEndedIterator for Windows<'a, T, I> {
+    fn next_back(&mut self) -> Option<Subset<'a, T, I>> {
+        self.m.next_back().map(|m| Subset { m })
+    }
+}
+
+
+/// Lending iterator over contiguous windows of `N` selected items,
+/// that yields arrays of mutable references to items of a window.
+/// 
+/// It does not implement `Iterator`, since consecutive windows overlap:
+/// references yielded for a window must be dropped before advancing to the next one.
+pub struct ArrayWindowsMut<'a, T, const N: usize, I = usize> {
+    ptr: *mut T,    // Points to the set
+    idxs: &'a [I],
+    pos: usize,    // Position of the first item of the next window
+    _marker: PhantomData<&'a mut T>
+}
+
+
+impl<'a, T, const N: usize, I: Idx> ArrayWindowsMut<'a, T, N, I> {
+    /// Advances to the next window and returns mutable references to its items,
+    /// or `None` if there are no windows left.
+    // Not `Iterator::next`: the yielded references borrow `self` mutably, so windows cannot outlive the call
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<[&mut T; N]> {
+        let window = self.idxs.get(self.pos..self.pos.checked_add(N)?)?;
+        self.pos += 1;
+        // Items of a window are selected by different indexes of a unique selection
+        Some(std::array::from_fn(|k| unsafe{&mut *self.ptr.add(window[k].index())}))
+    }
+    /// Returns the number of windows left.
+    pub fn len(&self) -> usize {
+        match self.pos.checked_add(N) {
+            Some(end) if end <= self.idxs.len() => self.idxs.len() - end + 1,
+            _ => 0
+        }
+    }
+    /// Returns `true` if there are no windows left.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_windows() {
+        let mut set = vec![0, 1, 2, 3];
+        let idxs = [3, 0, 1];
+        let mut subset = SubsetMut::new(&mut set, &idxs).unwrap();
+        assert_eq!(subset.windows(2).map(|w| w.iter().sum::<i32>()).collect::<Vec<_>>(), vec![3, 1]);
+        let mut pairs = subset.pairs_mut();
+        assert_eq!(pairs.len(), 2);
+        while let Some([a, b]) = pairs.next() {
+            std::mem::swap(a, b);
+        }
+        assert!(pairs.is_empty());
+        assert!(subset.array_windows_mut::<4>().next().is_none());
+        let subset: Subset<_> = subset.into();
+        assert_eq!(subset.windows(3).next().unwrap().iter().copied().collect::<Vec<_>>(), vec![0, 1, 3]);
+        assert_eq!(set, vec![1, 3, 2, 0]);
+    }
+}
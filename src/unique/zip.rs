@@ -0,0 +1,269 @@
+//! Subsets sharing one index list over several columns of a struct-of-arrays.
+
+use super::{is_unique, SubsetError, ValidatedIdxs};
+use std::marker::PhantomData;
+
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+
+/// Column of a struct-of-arrays: `&mut [T]` yields mutable references, `&[T]` immutable ones.
+/// 
+/// This trait is sealed: subsets trust `len` and `get` of these two implementations only.
+pub trait Column: sealed::Sealed {
+    /// Raw pointer to the first item.
+    type Ptr: Copy;
+    /// Reference to an item.
+    type Item<'s> where Self: 's;
+    /// Returns the number of items in the column.
+    fn len(&self) -> usize;
+    /// Returns `true` if the column has no items.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns a raw pointer to the first item.
+    fn as_ptr(&mut self) -> Self::Ptr;
+    /// Returns a reference to the item at `idx`.
+    /// 
+    /// # Safety
+    /// `idx` must be in bounds and no other live reference may alias a mutable item.
+    unsafe fn get<'s>(ptr: Self::Ptr, idx: usize) -> Self::Item<'s> where Self: 's;
+}
+
+
+impl<T> sealed::Sealed for &mut [T] {}
+impl<T> sealed::Sealed for &[T] {}
+
+
+impl<T> Column for &mut [T] {
+    type Ptr = *mut T;
+    type Item<'s> = &'s mut T where Self: 's;
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+    fn as_ptr(&mut self) -> *mut T {
+        self.as_mut_ptr()
+    }
+    unsafe fn get<'s>(ptr: *mut T, idx: usize) -> &'s mut T where Self: 's {
+        &mut *ptr.add(idx)
+    }
+}
+
+
+impl<T> Column for &[T] {
+    type Ptr = *const T;
+    type Item<'s> = &'s T where Self: 's;
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+    fn as_ptr(&mut self) -> *const T {
+        <[T]>::as_ptr(self)
+    }
+    unsafe fn get<'s>(ptr: *const T, idx: usize) -> &'s T where Self: 's {
+        &*ptr.add(idx)
+    }
+}
+
+
+/// Tuple of 2 to 8 columns.
+/// 
+/// This trait is sealed: subsets trust `min_len` and `get` of the tuple implementations only.
+pub trait Columns: sealed::Sealed {
+    /// Tuple of raw pointers to the first items.
+    type Ptrs: Copy;
+    /// Tuple of references to items in the same row.
+    type Item<'s> where Self: 's;
+    /// Returns the number of items in the shortest column.
+    fn min_len(&self) -> usize;
+    /// Returns raw pointers to the first items.
+    fn as_ptrs(&mut self) -> Self::Ptrs;
+    /// Returns references to the items of row `idx`.
+    /// 
+    /// # Safety
+    /// `idx` must be in bounds and no other live reference may alias a mutable item.
+    unsafe fn get<'s>(ptrs: Self::Ptrs, idx: usize) -> Self::Item<'s> where Self: 's;
+}
+
+
+macro_rules! impl_columns {
+    ($($c:ident $i:tt),+) => {
+        impl<$($c: Column),+> sealed::Sealed for ($($c,)+) {}
+
+        impl<$($c: Column),+> Columns for ($($c,)+) {
+            type Ptrs = ($($c::Ptr,)+);
+            type Item<'s> = ($($c::Item<'s>,)+) where Self: 's;
+            fn min_len(&self) -> usize {
+                let mut len = usize::MAX;
+                $(len = len.min(self.$i.len());)+
+                len
+            }
+            fn as_ptrs(&mut self) -> Self::Ptrs {
+                ($(self.$i.as_ptr(),)+)
+            }
+            unsafe fn get<'s>(ptrs: Self::Ptrs, idx: usize) -> Self::Item<'s> where Self: 's {
+                ($($c::get(ptrs.$i, idx),)+)
+            }
+        }
+    };
+}
+
+impl_columns!(A 0, B 1);
+impl_columns!(A 0, B 1, C 2);
+impl_columns!(A 0, B 1, C 2, D 3);
+impl_columns!(A 0, B 1, C 2, D 3, E 4);
+impl_columns!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_columns!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_columns!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+
+/// Unique subset of rows of several columns that iterates over tuples of references to items of selected rows.
+/// Indexes are checked once for all columns and are always `usize`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use subset::unique::*;
+/// let mut pos = vec![0.0, 1.0, 2.0, 3.0];
+/// let mut vel = vec![1.0, 1.0, -1.0, -1.0];
+/// let mass = vec![1.0, 2.0, 4.0, 8.0];
+/// let idxs = [3, 1];
+/// let mut rows = ZipSubsetMut::new((&mut pos[..], &mut vel[..], &mass[..]), &idxs).unwrap();
+/// for (p, v, m) in rows.iter_mut() {
+///     *v /= m;
+///     *p += *v;
+/// }
+/// assert_eq!(pos, vec![0.0, 1.5, 2.0, 2.875]);
+/// assert_eq!(vel, vec![1.0, 0.5, -1.0, -0.125]);
+/// ```
+pub struct ZipSubsetMut<'a, C> {
+    columns: C,
+    idxs: &'a [usize]
+}
+
+
+impl<'a, C: Columns> ZipSubsetMut<'a, C> {
+    /// Constructs a subset from a tuple of columns and indexes of the selected rows.
+    /// Both the uniqueness of the selected rows and the bounds of every column is checked.
+    /// 
+    /// # Errors
+    /// NotUnique, if there are duplicate indexes.
+    /// OutOfBounds, if any index is `>=` the length of any column.
+    pub fn new(columns: C, idxs: &'a [usize]) -> Result<Self, SubsetError> {
+        let min_len = columns.min_len();
+        if idxs.iter().any(|v| *v >= min_len) {
+            Err(SubsetError::OutOfBounds)
+        } else if !is_unique(idxs) {
+            Err(SubsetError::NotUnique)
+        } else { Ok(unsafe{Self::new_unchecked(columns, idxs)}) }
+    }
+    /// Constructs a subset from a tuple of columns and pre-validated indexes of the selected rows.
+    /// Only the lengths of the columns are checked.
+    /// 
+    /// # Errors
+    /// OutOfBounds, if any column is shorter than `idxs.max_len()`.
+    pub fn from_validated(columns: C, idxs: &'a ValidatedIdxs) -> Result<Self, SubsetError> {
+        if columns.min_len() < idxs.max_len() {
+            Err(SubsetError::OutOfBounds)
+        } else { Ok(unsafe{Self::new_unchecked(columns, idxs.idxs())}) }
+    }
+    /// Constructs a subset from a tuple of columns and indexes of the selected rows.
+    /// Neither the uniqueness of the selected rows, nor the bounds is checked.
+    /// 
+    /// # Safety
+    /// Every index must be less than the length of every column and no index may occur twice.
+    pub unsafe fn new_unchecked(columns: C, idxs: &'a [usize]) -> Self {
+        Self {
+            columns,
+            idxs
+        }
+    }
+    /// Returns the original columns.
+    pub fn columns(&self) -> &C {
+        &self.columns
+    }
+    /// Converts back to the original columns.
+    pub fn into_columns(self) -> C {
+        self.columns
+    }
+    /// Returns indexes of selected rows.
+    pub fn idxs(&self) -> &[usize] {
+        self.idxs
+    }
+    /// Returns references to the items of the `pos`-th selected row or `None` if `pos >= idxs().len()`.
+    pub fn get_mut(&mut self, pos: usize) -> Option<C::Item<'_>> {
+        let idx = *self.idxs.get(pos)?;
+        Some(unsafe{C::get(self.columns.as_ptrs(), idx)})
+    }
+    /// Returns an iterator over tuples of references to items of selected rows.
+    pub fn iter_mut(&mut self) -> ZipIterMut<'_, C> {
+        ZipIterMut {
+            ptrs: self.columns.as_ptrs(),
+            iter: self.idxs.iter(),
+            _marker: PhantomData
+        }
+    }
+}
+
+
+/// Double-ended iterator over tuples of references to items of selected rows.
+pub struct ZipIterMut<'s, C: Columns> {
+    ptrs: C::Ptrs,    // Point to the columns
+    iter: std::slice::Iter<'s, usize>,
+    _marker: PhantomData<&'s mut C>
+}
+
+
+impl<'s, C: Columns + 's> Iterator for ZipIterMut<'s, C> {
+    type Item = C::Item<'s>;
+    fn next(&mut self) -> Option<C::Item<'s>> {
+        let idx = self.iter.next()?;
+        Some(unsafe{C::get(self.ptrs, *idx)})
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<'s, C: Columns + 's> ExactSizeIterator for ZipIterMut<'s, C> {}
+
+
+impl<'s, C: Columns + 's> DoubleEndedIterator for ZipIterMut<'s, C> {
+    fn next_back(&mut self) -> Option<C::Item<'s>> {
+        let idx = self.iter.next_back()?;
+        Some(unsafe{C::get(self.ptrs, *idx)})
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_zip() {
+        let mut a = vec![0, 1, 2, 3, 4];
+        let mut b = vec!['a', 'b', 'c', 'd', 'e'];
+        let c = [10, 20, 30, 40];
+        assert_eq!(ZipSubsetMut::new((&mut a[..], &c[..]), &[1, 1]).err(), Some(SubsetError::NotUnique));
+        assert_eq!(ZipSubsetMut::new((&mut a[..], &c[..]), &[4]).err(), Some(SubsetError::OutOfBounds));
+        let idxs = [3, 0];
+        let mut rows = ZipSubsetMut::new((&mut a[..], &mut b[..], &c[..]), &idxs).unwrap();
+        for (x, y, z) in rows.iter_mut().rev() {
+            *x += z;
+            *y = y.to_ascii_uppercase();
+        }
+        let (x, _, z) = rows.get_mut(1).unwrap();
+        *x *= z;
+        assert!(rows.get_mut(2).is_none());
+        assert_eq!(a, vec![100, 1, 2, 43, 4]);
+        assert_eq!(b, vec!['A', 'b', 'c', 'D', 'e']);
+        let validated = ValidatedIdxs::new(vec![2, 4], 5).unwrap();
+        assert!(ZipSubsetMut::from_validated((&mut a[..], &c[..]), &validated).is_err());
+        let mut rows = ZipSubsetMut::from_validated((&mut a[..], &mut b[..]), &validated).unwrap();
+        assert_eq!(rows.iter_mut().len(), 2);
+    }
+}
@@ -0,0 +1,612 @@
+//! Traits unifying read and write access to selected items of all subset types.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use subset::{SubsetView, SubsetViewMut};
+//! 
+//! fn mean<S: SubsetView<f64>>(subset: &S) -> f64 {
+//!     subset.fold(0.0, |sum, v| sum + v) / subset.len() as f64
+//! }
+//! 
+//! let mut set = [1.0, 2.0, 3.0, 4.0];
+//! let idxs = [0, 2, 2];
+//! assert_eq!(mean(&subset::multi::Subset::new(&set, &idxs).unwrap()), 7.0 / 3.0);
+//! let idxs = [3, 1];
+//! let mut subset = subset::unique::SubsetMut::new(&mut set, &idxs).unwrap();
+//! assert_eq!(mean(&subset), 3.0);
+//! subset.scatter(&[0.0, 10.0]);
+//! assert_eq!(set, [1.0, 10.0, 3.0, 0.0]);
+//! ```
+
+use super::{compressed, multi, ranges, strided, unique, Idx};
+use std::iter::Map;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+
+/// Immutable access to selected items, implemented by every subset type.
+pub trait SubsetView<T> {
+    /// Double-ended iterator over immutable references to selected items.
+    type Iter<'s>: DoubleEndedIterator<Item = &'s T> where Self: 's, T: 's;
+    /// Double-ended iterator over indexes of selected items.
+    type Idxs<'s>: DoubleEndedIterator<Item = usize> where Self: 's;
+
+    /// Returns the number of selected items.
+    fn len(&self) -> usize;
+    /// Returns a reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    fn get(&self, pos: usize) -> Option<&T>;
+    /// Returns an iterator over indexes of selected items.
+    fn idxs(&self) -> Self::Idxs<'_>;
+    /// Returns an iterator over immutable references to selected items.
+    fn iter(&self) -> Self::Iter<'_>;
+
+    /// Returns `true` if no items are selected.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Folds selected items in selection order.
+    fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+    /// Returns the first selected item satisfying the predicate.
+    fn find<P: FnMut(&T) -> bool>(&self, mut predicate: P) -> Option<&T> {
+        self.iter().find(|v| predicate(v))
+    }
+    /// Returns the selection position of the first selected item satisfying the predicate.
+    fn position<P: FnMut(&T) -> bool>(&self, predicate: P) -> Option<usize> {
+        self.iter().position(predicate)
+    }
+    /// Checks whether any selected item is equal to `value`.
+    fn contains(&self, value: &T) -> bool where T: PartialEq {
+        self.iter().any(|v| v == value)
+    }
+    /// Clones selected items into a vector, in selection order.
+    fn gather(&self) -> Vec<T> where T: Clone {
+        self.iter().cloned().collect()
+    }
+    /// Clones selected items to the end of `out`, in selection order.
+    fn gather_into(&self, out: &mut Vec<T>) where T: Clone {
+        out.extend(self.iter().cloned())
+    }
+    /// Returns an iterator over all contiguous windows of `N` selected items, as arrays of references.
+    /// 
+    /// # Panics
+    /// Panics if `N == 0`
+    fn array_windows<const N: usize>(&self) -> ArrayWindows<'_, T, Self, N> {
+        assert_ne!(N, 0, "window size must be non-zero");
+        ArrayWindows {
+            subset: self,
+            starts: 0..self.len().saturating_sub(N - 1),
+            _marker: PhantomData
+        }
+    }
+    /// Returns an iterator over all contiguous windows of `n` selected items, as views borrowing this subset.
+    /// 
+    /// Subset types with an inherent `windows` method yield subsets of their own type instead,
+    /// call this one as `SubsetView::windows` to get views.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use subset::SubsetView;
+    /// use subset::ranges::*;
+    /// 
+    /// let set = [1, 2, 3, 4, 5, 6];
+    /// let subset = RangeSubset::new(&set, RangeList::new(vec![0..2, 4..6]).unwrap()).unwrap();
+    /// let sums: Vec<i32> = subset.windows(3).map(|w| w.fold(0, |sum, v| sum + v)).collect();
+    /// assert_eq!(sums, vec![8, 13]);
+    /// ```
+    /// 
+    /// # Panics
+    /// Panics if `n == 0`
+    fn windows(&self, n: usize) -> Windows<'_, T, Self> {
+        assert_ne!(n, 0, "window size must be non-zero");
+        Windows {
+            subset: self,
+            n,
+            starts: 0..self.len().saturating_sub(n - 1),
+            _marker: PhantomData
+        }
+    }
+}
+
+
+/// Mutable access to selected items, implemented only by subsets that select every item no more than once.
+pub trait SubsetViewMut<T>: SubsetView<T> {
+    /// Double-ended iterator over mutable references to selected items.
+    type IterMut<'s>: DoubleEndedIterator<Item = &'s mut T> where Self: 's, T: 's;
+
+    /// Returns a mutable reference to the `pos`-th selected item or `None` if `pos >= len()`.
+    fn get_mut(&mut self, pos: usize) -> Option<&mut T>;
+    /// Returns an iterator over mutable references to selected items.
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+
+    /// Assigns a clone of `value` to every selected item.
+    fn fill(&mut self, value: T) where T: Clone {
+        self.iter_mut().for_each(|v| *v = value.clone())
+    }
+    /// Returns a mutable reference to the first selected item satisfying the predicate.
+    fn find_mut<P: FnMut(&T) -> bool>(&mut self, mut predicate: P) -> Option<&mut T> {
+        self.iter_mut().find(|v| predicate(v))
+    }
+    /// Assigns clones of `values` to selected items, in selection order.
+    /// 
+    /// # Panics
+    /// Panics if `values.len() != self.len()`
+    fn scatter(&mut self, values: &[T]) where T: Clone {
+        assert_eq!(values.len(), self.len());
+        self.iter_mut().zip(values).for_each(|(v, value)| v.clone_from(value))
+    }
+}
+
+
+/// Double-ended iterator over contiguous windows of selected items of any subset type, as arrays of references.
+pub struct ArrayWindows<'s, T, S: ?Sized, const N: usize> {
+    subset: &'s S,
+    starts: Range<usize>,    // Positions of the first items of windows left to yield
+    _marker: PhantomData<&'s T>
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized, const N: usize> ArrayWindows<'s, T, S, N> {
+    fn window(&self, start: usize) -> [&'s T; N] {
+        let subset = self.subset;
+        std::array::from_fn(|k| subset.get(start + k).expect("position out of bounds"))
+    }
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized, const N: usize> Iterator for ArrayWindows<'s, T, S, N> {
+    type Item = [&'s T; N];
+    fn next(&mut self) -> Option<[&'s T; N]> {
+        self.starts.next().map(|start| self.window(start))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.starts.size_hint()
+    }
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized, const N: usize> ExactSizeIterator for ArrayWindows<'s, T, S, N> {}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized, const N: usize> DoubleEndedIterator for ArrayWindows<'s, T, S, N> {
+    fn next_back(&mut self) -> Option<[&'s T; N]> {
+        self.starts.next_back().map(|start| self.window(start))
+    }
+}
+
+
+/// Double-ended iterator over contiguous windows of selected items of any subset type, as views.
+pub struct Windows<'s, T, S: ?Sized> {
+    subset: &'s S,
+    n: usize,
+    starts: Range<usize>,    // Positions of the first items of windows left to yield
+    _marker: PhantomData<&'s T>
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized> Windows<'s, T, S> {
+    fn window(&self, start: usize) -> Window<'s, T, S> {
+        Window {
+            subset: self.subset,
+            start,
+            len: self.n,
+            _marker: PhantomData
+        }
+    }
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized> Iterator for Windows<'s, T, S> {
+    type Item = Window<'s, T, S>;
+    fn next(&mut self) -> Option<Window<'s, T, S>> {
+        self.starts.next().map(|start| self.window(start))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.starts.size_hint()
+    }
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized> ExactSizeIterator for Windows<'s, T, S> {}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized> DoubleEndedIterator for Windows<'s, T, S> {
+    fn next_back(&mut self) -> Option<Window<'s, T, S>> {
+        self.starts.next_back().map(|start| self.window(start))
+    }
+}
+
+
+/// View of `len` consecutive selected items of a subset, starting at position `start`.
+pub struct Window<'s, T, S: ?Sized> {
+    subset: &'s S,
+    start: usize,
+    len: usize,
+    _marker: PhantomData<&'s T>
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized> SubsetView<T> for Window<'s, T, S> {
+    type Iter<'w> = WindowIter<'w, T, S> where Self: 'w, T: 'w;
+    type Idxs<'w> = WindowIdxs<S::Idxs<'w>> where Self: 'w;
+    fn len(&self) -> usize {
+        self.len
+    }
+    fn get(&self, pos: usize) -> Option<&T> {
+        if pos < self.len {
+            self.subset.get(self.start + pos)
+        } else { None }
+    }
+    fn idxs(&self) -> WindowIdxs<S::Idxs<'_>> {
+        WindowIdxs {
+            idxs: self.subset.idxs(),
+            skip_front: self.start,
+            skip_back: self.subset.len() - self.start - self.len,
+            len: self.len
+        }
+    }
+    fn iter(&self) -> WindowIter<'_, T, S> {
+        WindowIter {
+            subset: self.subset,
+            positions: self.start..self.start + self.len,
+            _marker: PhantomData
+        }
+    }
+}
+
+
+/// Double-ended iterator over immutable references to items of a window.
+pub struct WindowIter<'s, T, S: ?Sized> {
+    subset: &'s S,
+    positions: Range<usize>,
+    _marker: PhantomData<&'s T>
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized> Iterator for WindowIter<'s, T, S> {
+    type Item = &'s T;
+    fn next(&mut self) -> Option<&'s T> {
+        let subset = self.subset;
+        self.positions.next().map(|pos| subset.get(pos).expect("position out of bounds"))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized> ExactSizeIterator for WindowIter<'s, T, S> {}
+
+
+impl<'s, T, S: SubsetView<T> + ?Sized> DoubleEndedIterator for WindowIter<'s, T, S> {
+    fn next_back(&mut self) -> Option<&'s T> {
+        let subset = self.subset;
+        self.positions.next_back().map(|pos| subset.get(pos).expect("position out of bounds"))
+    }
+}
+
+
+/// Double-ended iterator over indexes of items of a window.
+pub struct WindowIdxs<I> {
+    idxs: I,    // Indexes of all selected items of the subset
+    skip_front: usize,
+    skip_back: usize,
+    len: usize
+}
+
+
+impl<I: DoubleEndedIterator<Item = usize>> Iterator for WindowIdxs<I> {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let skip = std::mem::take(&mut self.skip_front);
+        self.idxs.nth(skip)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+
+impl<I: DoubleEndedIterator<Item = usize>> ExactSizeIterator for WindowIdxs<I> {}
+
+
+impl<I: DoubleEndedIterator<Item = usize>> DoubleEndedIterator for WindowIdxs<I> {
+    fn next_back(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let skip = std::mem::take(&mut self.skip_back);
+        self.idxs.nth_back(skip)
+    }
+}
+
+
+/// Iterator over positions of selected items given by a list of indexes.
+type IdxIter<'s, I> = Map<std::slice::Iter<'s, I>, fn(&I) -> usize>;
+
+
+fn index<I: Idx>(idx: &I) -> usize {
+    idx.index()
+}
+
+
+macro_rules! impl_subset_view {
+    ([$($gen:tt)*] $ty:ty, $idx:ty) => {
+        impl<'a, T, $($gen)*> SubsetView<T> for $ty {
+            type Iter<'s> = multi::Iter<'s, T, $idx> where Self: 's, T: 's;
+            type Idxs<'s> = IdxIter<'s, $idx> where Self: 's;
+            fn len(&self) -> usize {
+                self.idxs.len()
+            }
+            fn get(&self, pos: usize) -> Option<&T> {
+                self.idxs.get(pos).map(|idx| &self.set[idx.index()])
+            }
+            fn idxs(&self) -> Self::Idxs<'_> {
+                self.idxs.iter().map(index as fn(&$idx) -> usize)
+            }
+            fn iter(&self) -> multi::Iter<'_, T, $idx> {
+                multi::Iter {
+                    ptr: self.set.as_ptr(),
+                    iter: self.idxs.iter()
+                }
+            }
+        }
+    };
+}
+
+impl_subset_view!([I: Idx] multi::Subset<'a, T, I>, I);
+impl_subset_view!([I: Idx] multi::SubsetMut<'a, T, I>, I);
+impl_subset_view!([const N: usize] unique::FixedSubsetMut<'a, T, N>, usize);
+
+
+macro_rules! impl_unique_view {
+    ($ty:ty) => {
+        impl<'a, T, I: Idx> SubsetView<T> for $ty {
+            type Iter<'s> = multi::Iter<'s, T, I> where Self: 's, T: 's;
+            type Idxs<'s> = IdxIter<'s, I> where Self: 's;
+            fn len(&self) -> usize {
+                self.m.len()
+            }
+            fn get(&self, pos: usize) -> Option<&T> {
+                SubsetView::get(&self.m, pos)
+            }
+            fn idxs(&self) -> Self::Idxs<'_> {
+                SubsetView::idxs(&self.m)
+            }
+            fn iter(&self) -> multi::Iter<'_, T, I> {
+                self.m.iter()
+            }
+        }
+    };
+}
+
+impl_unique_view!(unique::Subset<'a, T, I>);
+impl_unique_view!(unique::SubsetMut<'a, T, I>);
+
+
+impl<'a, T, I: Idx> SubsetViewMut<T> for unique::SubsetMut<'a, T, I> {
+    type IterMut<'s> = unique::IterMut<'s, T, I> where Self: 's, T: 's;
+    fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        let idx = *self.m.idxs.get(pos)?;
+        Some(&mut self.m.set[idx.index()])
+    }
+    fn iter_mut(&mut self) -> unique::IterMut<'_, T, I> {
+        unique::SubsetMut::iter_mut(self)
+    }
+}
+
+
+impl<'a, T, const N: usize> SubsetViewMut<T> for unique::FixedSubsetMut<'a, T, N> {
+    type IterMut<'s> = unique::IterMut<'s, T> where Self: 's, T: 's;
+    fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        if pos < N {
+            Some(unique::FixedSubsetMut::get_mut(self, pos))
+        } else { None }
+    }
+    fn iter_mut(&mut self) -> unique::IterMut<'_, T> {
+        unique::FixedSubsetMut::iter_mut(self)
+    }
+}
+
+
+macro_rules! impl_strided_view {
+    ($ty:ty) => {
+        impl<'a, T> SubsetView<T> for $ty {
+            type Iter<'s> = strided::Iter<'s, T> where Self: 's, T: 's;
+            type Idxs<'s> = strided::StridedIdxs where Self: 's;
+            fn len(&self) -> usize {
+                self.count
+            }
+            fn get(&self, pos: usize) -> Option<&T> {
+                <$ty>::get(self, pos)
+            }
+            fn idxs(&self) -> strided::StridedIdxs {
+                <$ty>::idxs(self)
+            }
+            fn iter(&self) -> strided::Iter<'_, T> {
+                <$ty>::iter(self)
+            }
+        }
+    };
+}
+
+impl_strided_view!(strided::StridedSubset<'a, T>);
+impl_strided_view!(strided::StridedSubsetMut<'a, T>);
+
+
+impl<'a, T> SubsetViewMut<T> for strided::StridedSubsetMut<'a, T> {
+    type IterMut<'s> = strided::IterMut<'s, T> where Self: 's, T: 's;
+    fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        strided::StridedSubsetMut::get_mut(self, pos)
+    }
+    fn iter_mut(&mut self) -> strided::IterMut<'_, T> {
+        strided::StridedSubsetMut::iter_mut(self)
+    }
+}
+
+
+macro_rules! impl_range_view {
+    ($ty:ty) => {
+        impl<'a, T> SubsetView<T> for $ty {
+            type Iter<'s> = ranges::Iter<'s, T> where Self: 's, T: 's;
+            type Idxs<'s> = ranges::Idxs<'s> where Self: 's;
+            fn len(&self) -> usize {
+                self.ranges.len()
+            }
+            fn get(&self, pos: usize) -> Option<&T> {
+                <$ty>::get(self, pos)
+            }
+            fn idxs(&self) -> ranges::Idxs<'_> {
+                self.ranges.idxs()
+            }
+            fn iter(&self) -> ranges::Iter<'_, T> {
+                <$ty>::iter(self)
+            }
+        }
+    };
+}
+
+impl_range_view!(ranges::RangeSubset<'a, T>);
+impl_range_view!(ranges::RangeSubsetMut<'a, T>);
+
+
+impl<'a, T> SubsetViewMut<T> for ranges::RangeSubsetMut<'a, T> {
+    type IterMut<'s> = ranges::IterMut<'s, T> where Self: 's, T: 's;
+    fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        ranges::RangeSubsetMut::get_mut(self, pos)
+    }
+    fn iter_mut(&mut self) -> ranges::IterMut<'_, T> {
+        ranges::RangeSubsetMut::iter_mut(self)
+    }
+}
+
+
+macro_rules! impl_compressed_view {
+    ($ty:ty) => {
+        impl<'a, T> SubsetView<T> for $ty {
+            type Iter<'s> = compressed::SubsetIter<'s, T> where Self: 's, T: 's;
+            type Idxs<'s> = compressed::Iter<'s> where Self: 's;
+            fn len(&self) -> usize {
+                self.idxs.len()
+            }
+            fn get(&self, pos: usize) -> Option<&T> {
+                <$ty>::get(self, pos)
+            }
+            fn idxs(&self) -> compressed::Iter<'_> {
+                self.idxs.iter()
+            }
+            fn iter(&self) -> compressed::SubsetIter<'_, T> {
+                <$ty>::iter(self)
+            }
+        }
+    };
+}
+
+impl_compressed_view!(compressed::CompressedSubset<'a, T>);
+impl_compressed_view!(compressed::CompressedSubsetMut<'a, T>);
+
+
+impl<'a, T> SubsetViewMut<T> for compressed::CompressedSubsetMut<'a, T> {
+    type IterMut<'s> = compressed::SubsetIterMut<'s, T> where Self: 's, T: 's;
+    fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        compressed::CompressedSubsetMut::get_mut(self, pos)
+    }
+    fn iter_mut(&mut self) -> compressed::SubsetIterMut<'_, T> {
+        compressed::CompressedSubsetMut::iter_mut(self)
+    }
+}
+
+
+#[cfg(feature = "roaring")]
+macro_rules! impl_roaring_view {
+    ($ty:ty) => {
+        impl<'a, T> SubsetView<T> for $ty {
+            type Iter<'s> = compressed::RoaringIter<'s, T> where Self: 's, T: 's;
+            type Idxs<'s> = Map<roaring::bitmap::Iter<'s>, fn(u32) -> usize> where Self: 's;
+            fn len(&self) -> usize {
+                <$ty>::len(self)
+            }
+            fn get(&self, pos: usize) -> Option<&T> {
+                <$ty>::get(self, pos)
+            }
+            fn idxs(&self) -> Self::Idxs<'_> {
+                self.bitmap.iter().map((|idx| idx as usize) as fn(u32) -> usize)
+            }
+            fn iter(&self) -> compressed::RoaringIter<'_, T> {
+                <$ty>::iter(self)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "roaring")]
+impl_roaring_view!(compressed::RoaringSubset<'a, T>);
+#[cfg(feature = "roaring")]
+impl_roaring_view!(compressed::RoaringSubsetMut<'a, T>);
+
+
+#[cfg(feature = "roaring")]
+impl<'a, T> SubsetViewMut<T> for compressed::RoaringSubsetMut<'a, T> {
+    type IterMut<'s> = compressed::RoaringIterMut<'s, T> where Self: 's, T: 's;
+    fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        compressed::RoaringSubsetMut::get_mut(self, pos)
+    }
+    fn iter_mut(&mut self) -> compressed::RoaringIterMut<'_, T> {
+        compressed::RoaringSubsetMut::iter_mut(self)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn sum<S: SubsetView<i32>>(subset: &S) -> i32 {
+        subset.fold(0, |accum, v| accum + v)
+    }
+
+    fn negate<S: SubsetViewMut<i32>>(subset: &mut S) {
+        subset.iter_mut().for_each(|v| *v = -*v)
+    }
+
+    #[test]
+    fn test_view() {
+        let mut set = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        let idxs = vec![2, 4, 7, 4];
+        let subset = multi::Subset::new(&set, &idxs).unwrap();
+        assert_eq!(sum(&subset), 19);
+        assert_eq!(SubsetView::get(&subset, 3), Some(&5));
+        assert_eq!(SubsetView::get(&subset, 4), None);
+        assert_eq!(subset.position(|v| *v == 2), Some(2));
+        assert_eq!(subset.array_windows::<3>().map(|[a, b, c]| a + b + c).collect::<Vec<_>>(), vec![14, 12]);
+        assert!(!subset.contains(&9));
+        let idxs = vec![2, 4, 7];
+        let mut subset = unique::SubsetMut::new(&mut set, &idxs).unwrap();
+        negate(&mut subset);
+        assert_eq!(subset.gather(), vec![-7, -5, -2]);
+        *subset.find_mut(|v| *v == -5).unwrap() = 50;
+        assert_eq!(SubsetView::idxs(&subset).rev().collect::<Vec<_>>(), vec![7, 4, 2]);
+        let mut subset = unique::FixedSubsetMut::new(&mut set, [0, 1]).unwrap();
+        subset.fill(1);
+        assert_eq!(SubsetViewMut::get_mut(&mut subset, 2), None);
+        assert_eq!(sum(&subset), 2);
+        assert_eq!(set, vec![1, 1, -7, 6, 50, 4, 3, -2, 1, 0]);
+        let subset = strided::StridedSubset::new(&set, 1, 2, 5).unwrap();
+        let mut windows = SubsetView::windows(&subset, 3);
+        assert_eq!(windows.len(), 3);
+        let window = windows.next_back().unwrap();
+        assert_eq!((sum(&window), SubsetView::get(&window, 3)), (2, None));
+        assert_eq!(window.idxs().rev().collect::<Vec<_>>(), vec![9, 7, 5]);
+        assert_eq!(window.iter().rev().copied().collect::<Vec<_>>(), vec![0, -2, 4]);
+        assert_eq!(SubsetView::windows(&subset, 6).count(), 0);
+    }
+}